@@ -1,126 +1,2678 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, panic_with_error, symbol_short, xdr::ToXdr, Address,
-    Bytes, BytesN, Env, String, Symbol,
+    contract, contracterror, contractimpl, panic_with_error, symbol_short, token, xdr::ToXdr,
+    Address, Bytes, BytesN, Env, Map, String, Symbol, TryFromVal, Val, Vec,
 };
 
+use ed25519_dalek::{Signature, VerifyingKey};
+
 mod storage_types;
-use storage_types::{DataKey, WrapRecord};
+use storage_types::{
+    ContractConfig, DataKey, GlobalStats, LegacyDataKey, LegacyU64WrapRecord, LegacyWrapRecord,
+    MintEntry, Page, Profile, StorageStats, TokenMetadata, WrapRecord,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ContractError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    WrapAlreadyExists = 4,
+    InvalidSignature = 5,
+    UriTooLong = 6,
+    InvalidSignerSet = 7,
+    SignerNotFound = 8,
+    InvalidPeriod = 9,
+    BatchTooLarge = 10,
+    CosignRequired = 11,
+    MintNotStarted = 12,
+    AlreadyMigrated = 13,
+    InvalidFee = 14,
+    WrapNotFound = 15,
+    InvalidPubKey = 16,
+    Paused = 17,
+    CorruptRecord = 18,
+    MaxSupplyReached = 19,
+    ControlsRenounced = 20,
+    SelfMintDisallowed = 21,
+    CountOverflow = 22,
+    GlobalRateExceeded = 23,
+    AdminRenounced = 24,
+    PrerequisiteMissing = 25,
+    SignatureReused = 26,
+    NotEligible = 27,
+    ConsentInvalid = 28,
+    WrongContract = 29,
+    UserPeriodLimit = 30,
+    SbtTransferNotAllowed = 32,
+    LinkedPeriodMissing = 33,
+    DuplicateHash = 34,
+    PeriodTooHigh = 35,
+}
+
+/// Longest base URI accepted by `set_base_uri`, leaving room for the 64 hex chars of a hash.
+const MAX_BASE_URI_LEN: u32 = 192;
+
+/// Layout version leading the data of every registry event. Bump it whenever any
+/// event's data layout changes.
+pub const EVENT_SCHEMA: u32 = 1;
+
+/// Leading topic on every event unless the admin configures another namespace.
+const DEFAULT_EVENT_PREFIX: Symbol = symbol_short!("wrap");
+
+/// Enough for the current and next key to overlap during a rotation.
+const MAX_SIGNING_KEYS: u32 = 2;
+
+/// Persistent entries are extended to roughly one year of ledgers.
+const TTL_ONE_YEAR: u32 = 17280 * 365;
+// Storage layout written by this version; v1 keyed periods by Symbol
+const SCHEMA_VERSION: u32 = 2;
+
+/// Reserved period for a user's lifetime wrap, which the admin may overwrite.
+pub const ALL_TIME_PERIOD: u64 = 0;
+
+/// Field count of `LegacyU64WrapRecord`, the record shape before any fields were added.
+const LEGACY_RECORD_FIELDS: u32 = 4;
+
+/// Hash algorithm assumed for `data_hash` when the minter doesn't specify one.
+const DEFAULT_HASH_ALGO: Symbol = symbol_short!("sha256");
+
+/// Most entries a batch call (`mint_wrap_batch`, `revoke_period`) accepts, keeping it well inside the CPU budget.
+pub const MAX_BATCH: u32 = 50;
+
+/// Most index entries a cross-user read walks for each user.
+const MAX_INDEX_SCAN: u32 = 500;
+// Default bound on a user's period index when MaxPeriodsPerUser is unset
+const DEFAULT_MAX_PERIODS_PER_USER: u32 = 500;
+// Mints kept in the RecentMints ring buffer
+const RECENT_MINTS: u32 = 20;
+// Holders tracked by the wrap-count leaderboard
+const LEADERBOARD_SIZE: u32 = 100;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+// Longest message `signature_valid` checks; a mint payload with every term is well under it
+const MAX_CHECKED_PAYLOAD_LEN: u32 = 512;
+
+/// Fails once the admin has permanently renounced control.
+fn require_not_renounced(e: &Env) {
+    if e.storage()
+        .instance()
+        .get(&DataKey::Renounced)
+        .unwrap_or(false)
+    {
+        panic_with_error!(e, ContractError::AdminRenounced);
+    }
+}
+
+/// Loads the admin and requires its authorization for a configuration change, which
+/// is refused once controls have been renounced.
+fn require_admin(e: &Env) -> Address {
+    let admin = require_minting_admin(e);
+    if e.storage()
+        .instance()
+        .get(&DataKey::ControlsRenounced)
+        .unwrap_or(false)
+    {
+        panic_with_error!(e, ContractError::ControlsRenounced);
+    }
+    admin
+}
+
+/// Loads the admin and requires its authorization for minting, which keeps working
+/// after controls have been renounced.
+fn require_minting_admin(e: &Env) -> Address {
+    require_not_renounced(e);
+    let admin: Address = e
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap_or_else(|| panic_with_error!(e, ContractError::NotInitialized));
+    admin.require_auth();
+    admin
+}
+
+/// Signing key slots in registration order. A removed key leaves its slot empty, so the
+/// index a signature names keeps pointing at the same key for as long as it is live.
+fn signing_key_slots(e: &Env) -> Vec<Option<BytesN<32>>> {
+    let Some(raw) = e.storage().instance().get::<_, Val>(&DataKey::AdminPubKey) else {
+        return Vec::new(e);
+    };
+    // Deployments from before key rotation stored the one key bare; it is slot 0
+    match BytesN::<32>::try_from_val(e, &raw) {
+        Ok(key) => Vec::from_array(e, [Some(key)]),
+        Err(_) => Vec::try_from_val(e, &raw)
+            .unwrap_or_else(|_| panic_with_error!(e, ContractError::CorruptRecord)),
+    }
+}
+
+/// Signing keys accepted for wrap signatures, oldest first.
+fn signing_keys(e: &Env) -> Vec<BytesN<32>> {
+    let mut keys = Vec::new(e);
+    for key in signing_key_slots(e).iter().flatten() {
+        keys.push_back(key);
+    }
+    keys
+}
+
+/// The live signing key in slot `key_index`, if any.
+fn signing_key(e: &Env, key_index: u32) -> Option<BytesN<32>> {
+    signing_key_slots(e).get(key_index).flatten()
+}
+
+/// Parses a legacy period symbol such as `2024_01` into its numeric form (`202401`).
+///
+/// Symbols are read through their XDR encoding: a 4-byte type tag, a 4-byte
+/// big-endian length, then the characters. Underscores are ignored; anything
+/// other than digits is rejected.
+fn parse_period_symbol(e: &Env, period: &Symbol) -> u64 {
+    let xdr = period.clone().to_xdr(e);
+    let len = u32::from_be_bytes([
+        xdr.get_unchecked(4),
+        xdr.get_unchecked(5),
+        xdr.get_unchecked(6),
+        xdr.get_unchecked(7),
+    ]);
+
+    let mut value: u64 = 0;
+    let mut digits = 0;
+    for i in 8..8 + len {
+        match xdr.get_unchecked(i) {
+            b'_' => continue,
+            c @ b'0'..=b'9' => {
+                value = value
+                    .checked_mul(10)
+                    .and_then(|v| v.checked_add((c - b'0') as u64))
+                    .unwrap_or_else(|| panic_with_error!(e, ContractError::InvalidPeriod));
+                digits += 1;
+            }
+            _ => panic_with_error!(e, ContractError::InvalidPeriod),
+        }
+    }
+    if digits == 0 {
+        panic_with_error!(e, ContractError::InvalidPeriod);
+    }
+    value
+}
+
+/// Decodes the stored wrap for `(user, period)`. Records still in the original four-field
+/// shape are filled in with the defaults `LegacyU64WrapRecord` documents; a value of any
+/// other shape fails with `CorruptRecord`.
+fn try_load_wrap(
+    e: &Env,
+    user: &Address,
+    period: u64,
+) -> Result<Option<WrapRecord>, ContractError> {
+    let Some(raw) = e
+        .storage()
+        .persistent()
+        .get::<_, Val>(&DataKey::Wrap(user.clone(), period))
+    else {
+        return Ok(None);
+    };
+    // Decoding a struct from a map with the wrong field count traps rather than failing,
+    // so pick the shape by length first
+    let fields = Map::<Symbol, Val>::try_from_val(e, &raw)
+        .map_err(|_| ContractError::CorruptRecord)?
+        .len();
+    if fields != LEGACY_RECORD_FIELDS {
+        return WrapRecord::try_from_val(e, &raw)
+            .map(Some)
+            .map_err(|_| ContractError::CorruptRecord);
+    }
+    let legacy =
+        LegacyU64WrapRecord::try_from_val(e, &raw).map_err(|_| ContractError::CorruptRecord)?;
+    Ok(Some(WrapRecord {
+        timestamp: legacy.timestamp,
+        data_hash: legacy.data_hash,
+        archetype: legacy.archetype,
+        period: legacy.period,
+        hash_algo: DEFAULT_HASH_ALGO,
+        ledger_seq: 0,
+        consented: false,
+        global_id: u64::MAX,
+        linked_period: None,
+        // Only the admin's signing key could mint before minters existed
+        minted_by: e
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| e.current_contract_address()),
+        signer_index: None,
+    }))
+}
+
+/// `try_load_wrap` for callers that can't return the error.
+fn load_wrap(e: &Env, user: &Address, period: u64) -> Option<WrapRecord> {
+    try_load_wrap(e, user, period).unwrap_or_else(|err| panic_with_error!(e, err))
+}
+
+/// Loads every record in the user's period index, in ascending period order.
+fn user_wraps(e: &Env, user: &Address) -> Vec<WrapRecord> {
+    let periods: Vec<u64> = e
+        .storage()
+        .persistent()
+        .get(&DataKey::UserPeriods(user.clone()))
+        .unwrap_or_else(|| Vec::new(e));
+    let mut records = Vec::new(e);
+    for period in periods.iter() {
+        if let Some(record) = load_wrap(e, user, period) {
+            records.push_back(record);
+        }
+    }
+    records
+}
+
+/// Seconds a wrap stays live after minting, 0 meaning wraps never expire.
+fn wrap_lifetime(e: &Env) -> u64 {
+    e.storage()
+        .instance()
+        .get(&DataKey::WrapLifetime)
+        .unwrap_or(0)
+}
+
+/// Whether `record` has outlived `lifetime` at the current ledger time.
+fn record_expired(e: &Env, record: &WrapRecord, lifetime: u64) -> bool {
+    lifetime != 0 && record.timestamp.saturating_add(lifetime) <= e.ledger().timestamp()
+}
+
+/// Whether wrap reads are restricted to their owner or the admin.
+fn is_private(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&DataKey::PrivateMode)
+        .unwrap_or(false)
+}
+
+/// Gate for every read that returns a wrap record or data taken from one. Outside private
+/// mode anyone may read. In private mode a read without a `viewer` needs the owner's auth;
+/// an authorized `viewer` must be the owner or the admin, otherwise `Unauthorized`.
+fn authorize_wrap_read(
+    e: &Env,
+    user: &Address,
+    viewer: Option<&Address>,
+) -> Result<(), ContractError> {
+    if !is_private(e) {
+        return Ok(());
+    }
+    match viewer {
+        None => user.require_auth(),
+        Some(viewer) => {
+            let admin: Option<Address> = e.storage().instance().get(&DataKey::Admin);
+            if viewer != user && admin.as_ref() != Some(viewer) {
+                return Err(ContractError::Unauthorized);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `authorize_wrap_read` for the owner-only reads, which have no way to return the error.
+fn require_wrap_reader(e: &Env, user: &Address) {
+    if let Err(err) = authorize_wrap_read(e, user, None) {
+        panic_with_error!(e, err);
+    }
+}
+
+/// Sums the archetype weights of `wraps`; unweighted archetypes count 1.
+fn score_of(e: &Env, wraps: &Vec<WrapRecord>) -> u64 {
+    wraps
+        .iter()
+        .map(|record| {
+            e.storage()
+                .persistent()
+                .get::<_, u32>(&DataKey::ArchetypeWeight(record.archetype))
+                .unwrap_or(1) as u64
+        })
+        .sum()
+}
+
+/// Namespace symbol published as the first topic of every contract event except the
+/// SEP-41 `mint` balance event.
+fn event_prefix(e: &Env) -> Symbol {
+    e.storage()
+        .instance()
+        .get(&DataKey::EventPrefix)
+        .unwrap_or(DEFAULT_EVENT_PREFIX)
+}
+
+/// Returns the per-ledger mint cap (0 = unlimited) and how many mints the current ledger
+/// has used so far.
+fn ledger_mint_usage(e: &Env) -> (u32, u32) {
+    let cap: u32 = e
+        .storage()
+        .instance()
+        .get(&DataKey::LedgerMintCap)
+        .unwrap_or(0);
+    let ledger = e.ledger().sequence();
+    let (last_ledger, count): (u32, u32) = e
+        .storage()
+        .instance()
+        .get(&DataKey::LedgerMintCount)
+        .unwrap_or((ledger, 0));
+    (cap, if last_ledger == ledger { count } else { 0 })
+}
+
+/// Counts a mint against the admin-set per-ledger cap, resetting when the ledger advances.
+fn consume_ledger_mint_allowance(e: &Env) {
+    let (cap, count) = ledger_mint_usage(e);
+    if cap == 0 {
+        return;
+    }
+    if count >= cap {
+        panic_with_error!(e, ContractError::GlobalRateExceeded);
+    }
+    e.storage().instance().set(
+        &DataKey::LedgerMintCount,
+        &(e.ledger().sequence(), count + 1),
+    );
+}
+
+/// Increments a contract-wide counter kept in instance storage.
+fn increment_global(e: &Env, key: &DataKey) -> u32 {
+    let value: u32 = e.storage().instance().get(key).unwrap_or(0);
+    let value = value
+        .checked_add(1)
+        .unwrap_or_else(|| panic_with_error!(e, ContractError::CountOverflow));
+    e.storage().instance().set(key, &value);
+    value
+}
+
+/// Decrements a contract-wide counter kept in instance storage, stopping at zero.
+fn decrement_global(e: &Env, key: &DataKey) {
+    let value: u32 = e.storage().instance().get(key).unwrap_or(0);
+    e.storage().instance().set(key, &value.saturating_sub(1));
+}
+
+/// Moves `user` to its place for `count` in the bounded leaderboard, dropping it at zero.
+/// Ties keep the holder who reached the count first ahead.
+fn update_leaderboard(e: &Env, user: &Address, count: u32) {
+    let mut board: Vec<(Address, u32)> = e
+        .storage()
+        .persistent()
+        .get(&DataKey::CountLeaderboard)
+        .unwrap_or_else(|| Vec::new(e));
+    if let Some(pos) = board.iter().position(|(holder, _)| holder == *user) {
+        board.remove(pos as u32);
+    }
+    if count > 0 {
+        let pos = board
+            .iter()
+            .position(|(_, held)| held < count)
+            .map_or(board.len(), |pos| pos as u32);
+        if pos < LEADERBOARD_SIZE {
+            board.insert(pos, (user.clone(), count));
+        }
+        while board.len() > LEADERBOARD_SIZE {
+            board.pop_back();
+        }
+    }
+    e.storage()
+        .persistent()
+        .set(&DataKey::CountLeaderboard, &board);
+    e.storage()
+        .persistent()
+        .extend_ttl(&DataKey::CountLeaderboard, TTL_ONE_YEAR, TTL_ONE_YEAR);
+}
+
+/// Adds `archetype` to the list of archetypes ever minted if it is new.
+fn note_archetype(e: &Env, archetype: &Symbol) {
+    let mut archetypes: Vec<Symbol> = e
+        .storage()
+        .persistent()
+        .get(&DataKey::SeenArchetypes)
+        .unwrap_or_else(|| Vec::new(e));
+    if !archetypes.contains(archetype) {
+        archetypes.push_back(archetype.clone());
+        e.storage()
+            .persistent()
+            .set(&DataKey::SeenArchetypes, &archetypes);
+    }
+    e.storage()
+        .persistent()
+        .extend_ttl(&DataKey::SeenArchetypes, TTL_ONE_YEAR, TTL_ONE_YEAR);
+}
+
+/// Adjusts the number of wraps held with `archetype` by `added - removed`, stopping at zero.
+fn shift_archetype_count(e: &Env, archetype: &Symbol, added: u32, removed: u32) {
+    let key = DataKey::ArchetypeCount(archetype.clone());
+    let count: u32 = e.storage().persistent().get(&key).unwrap_or(0);
+    let count = count
+        .checked_add(added)
+        .unwrap_or_else(|| panic_with_error!(e, ContractError::CountOverflow))
+        .saturating_sub(removed);
+    e.storage().persistent().set(&key, &count);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+}
+
+/// Most periods a single user's index may hold.
+fn max_periods_per_user(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::MaxPeriodsPerUser)
+        .unwrap_or(DEFAULT_MAX_PERIODS_PER_USER)
+}
+
+/// Hands out the next contract-wide wrap id.
+fn next_wrap_id(e: &Env) -> u64 {
+    let id: u64 = e
+        .storage()
+        .instance()
+        .get(&DataKey::NextWrapId)
+        .unwrap_or(0);
+    e.storage().instance().set(&DataKey::NextWrapId, &(id + 1));
+    id
+}
+
+/// Stores a new wrap record and bumps the holder's count and period index.
+fn insert_wrap(e: &Env, user: &Address, record: &WrapRecord) {
+    let period = record.period;
+
+    // Keep index-walking reads bounded
+    let held = e
+        .storage()
+        .persistent()
+        .get::<_, Vec<u64>>(&DataKey::UserPeriods(user.clone()))
+        .map_or(0, |periods| periods.len());
+    if held >= max_periods_per_user(e) {
+        panic_with_error!(e, ContractError::UserPeriodLimit);
+    }
+    let wrap_key = DataKey::Wrap(user.clone(), period);
+
+    // Store in persistent and extend TTL to ~1 year
+    e.storage().persistent().set(&wrap_key, record);
+    e.storage()
+        .persistent()
+        .extend_ttl(&wrap_key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+
+    // Update Balance (Switch to Persistent)
+    let count_key = DataKey::WrapCount(user.clone());
+    let current_count: u32 = e.storage().persistent().get(&count_key).unwrap_or(0);
+    let new_count = current_count
+        .checked_add(1)
+        .unwrap_or_else(|| panic_with_error!(e, ContractError::CountOverflow));
+    e.storage().persistent().set(&count_key, &new_count);
+    e.storage()
+        .persistent()
+        .extend_ttl(&count_key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+
+    // SEP-41 style balance update for token-aware wallets. Its data stays the bare
+    // balance the standard expects, so it carries no EVENT_SCHEMA
+    e.events()
+        .publish((symbol_short!("mint"), user.clone()), new_count as i128);
+    update_leaderboard(e, user, new_count);
+
+    let milestones: Vec<u32> = e
+        .storage()
+        .instance()
+        .get(&DataKey::Milestones)
+        .unwrap_or_else(|| Vec::new(e));
+    if milestones.contains(new_count) {
+        e.events().publish(
+            (event_prefix(e), symbol_short!("milestn"), user.clone()),
+            (EVENT_SCHEMA, new_count),
+        );
+    }
+
+    count_wrap_stats(e, record, held == 0);
+    // Ring buffer of the latest mints for clients that can't follow events
+    let mut recent: Vec<(Address, u64)> = e
+        .storage()
+        .persistent()
+        .get(&DataKey::RecentMints)
+        .unwrap_or_else(|| Vec::new(e));
+    recent.push_back((user.clone(), period));
+    if recent.len() > RECENT_MINTS {
+        recent.pop_front();
+    }
+    e.storage().persistent().set(&DataKey::RecentMints, &recent);
+    e.storage()
+        .persistent()
+        .extend_ttl(&DataKey::RecentMints, TTL_ONE_YEAR, TTL_ONE_YEAR);
+
+    index_period(e, user, period);
+}
+
+/// Counts a newly stored wrap in the contract-wide stats: supply, holders (when
+/// `new_holder`), distinct periods, archetype counts and the period's mint total. The stats
+/// cover indexed wraps, so a holder is a user with a non-empty period index.
+fn count_wrap_stats(e: &Env, record: &WrapRecord, new_holder: bool) {
+    increment_global(e, &DataKey::TotalSupply);
+    if new_holder {
+        increment_global(e, &DataKey::HolderCount);
+    }
+    let seen_key = DataKey::PeriodSeen(record.period);
+    if !e.storage().persistent().has(&seen_key) {
+        e.storage().persistent().set(&seen_key, &true);
+        e.storage()
+            .persistent()
+            .extend_ttl(&seen_key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+        increment_global(e, &DataKey::DistinctPeriods);
+    }
+    note_archetype(e, &record.archetype);
+    shift_archetype_count(e, &record.archetype, 1, 0);
+
+    let minted_key = DataKey::PeriodMinted(record.period);
+    let minted: u32 = e.storage().persistent().get(&minted_key).unwrap_or(0);
+    let minted = minted
+        .checked_add(1)
+        .unwrap_or_else(|| panic_with_error!(e, ContractError::CountOverflow));
+    e.storage().persistent().set(&minted_key, &minted);
+    e.storage()
+        .persistent()
+        .extend_ttl(&minted_key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+}
+
+/// Indexes a wrap that is stored and already in `WrapCount` but predates the contract-wide
+/// stats, counting it there and on the leaderboard. Used by the migration entry points.
+fn adopt_wrap(e: &Env, user: &Address, record: &WrapRecord) {
+    let new_holder = StellarWrapContract::list_periods(e.clone(), user.clone()).is_empty();
+    count_wrap_stats(e, record, new_holder);
+    let count: u32 = e
+        .storage()
+        .persistent()
+        .get(&DataKey::WrapCount(user.clone()))
+        .unwrap_or(0);
+    update_leaderboard(e, user, count);
+    index_period(e, user, record.period);
+}
+
+/// Fails with `AlreadyMigrated` once the stored layout is at `SCHEMA_VERSION`.
+fn require_unmigrated(e: &Env) {
+    let version: u32 = e
+        .storage()
+        .instance()
+        .get(&DataKey::SchemaVersion)
+        .unwrap_or(1);
+    if version >= SCHEMA_VERSION {
+        panic_with_error!(e, ContractError::AlreadyMigrated);
+    }
+}
+
+/// Adds `period` to the user's sorted period index.
+fn index_period(e: &Env, user: &Address, period: u64) {
+    // Keep the user's period index sorted for paging
+    let periods_key = DataKey::UserPeriods(user.clone());
+    let mut periods: Vec<u64> = e
+        .storage()
+        .persistent()
+        .get(&periods_key)
+        .unwrap_or_else(|| Vec::new(e));
+    if let Err(pos) = periods.binary_search(period) {
+        periods.insert(pos, period);
+    }
+    e.storage().persistent().set(&periods_key, &periods);
+    e.storage()
+        .persistent()
+        .extend_ttl(&periods_key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+}
+
+/// Transfers the configured mint fee from `payer` to the admin. A zero fee or an
+/// unset fee token charges nothing.
+fn charge_mint_fee(e: &Env, payer: &Address) {
+    let amount: i128 = e.storage().instance().get(&DataKey::FeeAmount).unwrap_or(0);
+    if amount == 0 {
+        return;
+    }
+    let Some(fee_token) = e.storage().instance().get::<_, Address>(&DataKey::FeeToken) else {
+        return;
+    };
+    let admin: Address = e
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap_or_else(|| panic_with_error!(e, ContractError::NotInitialized));
+    token::Client::new(e, &fee_token).transfer(payer, &admin, &amount);
+}
+
+/// Ed25519 public key behind a Stellar account address, or `None` for contract addresses.
+///
+/// An account address encodes as a 4-byte ScVal tag, a 4-byte ScAddress tag (0 =
+/// account), a 4-byte key type (0 = ed25519), then the 32 key bytes.
+fn account_pubkey(e: &Env, address: &Address) -> Option<BytesN<32>> {
+    let xdr = address.clone().to_xdr(e);
+    if xdr.len() != 44 || xdr.slice(4..12) != Bytes::from_array(e, &[0u8; 8]) {
+        return None;
+    }
+    let mut key = [0u8; 32];
+    xdr.slice(12..44).copy_into_slice(&mut key);
+    Some(BytesN::from_array(e, &key))
+}
+
+/// Publishes why an SEP-41 token operation was refused and returns the matching error.
+fn reject_sbt_operation(e: &Env, from: Address, reason: Symbol) -> Result<(), ContractError> {
+    e.events().publish(
+        (event_prefix(e), Symbol::new(e, "sbt_reject"), from),
+        (EVENT_SCHEMA, reason),
+    );
+    Err(ContractError::SbtTransferNotAllowed)
+}
+
+/// How a mint was authorized.
+enum MintAuth {
+    /// Off-chain admin signature over the mint payload
+    Signed {
+        signature: BytesN<64>,
+        /// Index into the signing key set the signature was made with
+        key_index: u32,
+        /// Signature from the other registered key, required for co-signed archetypes
+        cosignature: Option<BytesN<64>>,
+    },
+    /// On-chain minter (admin or designated minter) that has already authorized the call
+    Minter(Address),
+}
+
+/// Inputs shared by every mint entry point.
+struct MintArgs {
+    user: Address,
+    period: u64,
+    archetype: Symbol,
+    data_hash: BytesN<32>,
+    hash_algo: Symbol,
+    /// Whether the recipient signed an acknowledgment of the wrap
+    consented: bool,
+    /// Earlier period of the same user this wrap corrects or continues
+    linked_period: Option<u64>,
+    /// Period the user must already hold, committed to by the signature
+    requires: Option<u64>,
+    auth: MintAuth,
+}
+
+/// Deletes the user's wrap for `period`, updating their count and index.
+/// Returns false if the user didn't hold it.
+fn revoke(e: &Env, user: &Address, period: u64) -> bool {
+    let wrap_key = DataKey::Wrap(user.clone(), period);
+    let Some(record) = load_wrap(e, user, period) else {
+        return false;
+    };
+    e.storage().persistent().remove(&wrap_key);
+    e.storage()
+        .persistent()
+        .remove(&DataKey::UserHash(user.clone(), record.data_hash.clone()));
+    // A wrap minted again for this period starts unacknowledged
+    e.storage()
+        .persistent()
+        .remove(&DataKey::Acked(user.clone(), period));
+
+    let count_key = DataKey::WrapCount(user.clone());
+    let current_count: u32 = e.storage().persistent().get(&count_key).unwrap_or(0);
+    let new_count = current_count.saturating_sub(1);
+    e.storage().persistent().set(&count_key, &new_count);
+    update_leaderboard(e, user, new_count);
+
+    // Remove just this period so the index stays sorted. A wrap that was never indexed
+    // was never in the contract-wide stats either
+    let periods_key = DataKey::UserPeriods(user.clone());
+    let mut periods: Vec<u64> = e
+        .storage()
+        .persistent()
+        .get(&periods_key)
+        .unwrap_or_else(|| Vec::new(e));
+    if let Ok(pos) = periods.binary_search(period) {
+        periods.remove(pos);
+        e.storage().persistent().set(&periods_key, &periods);
+        shift_archetype_count(e, &record.archetype, 0, 1);
+        decrement_global(e, &DataKey::TotalSupply);
+        if periods.is_empty() {
+            decrement_global(e, &DataKey::HolderCount);
+        }
+    }
+
+    e.events().publish(
+        (
+            event_prefix(e),
+            symbol_short!("revoke"),
+            user.clone(),
+            period,
+        ),
+        (EVENT_SCHEMA,),
+    );
+    true
+}
+
+/// Checks an Ed25519 signature in contract code, returning false where `ed25519_verify`
+/// would trap. It costs far more CPU than the host function, so it is only used where a bad
+/// signature must be reported rather than abort; admin mint signatures still go through
+/// `ed25519_verify`.
+fn signature_valid(pubkey: &BytesN<32>, message: &Bytes, signature: &BytesN<64>) -> bool {
+    let len = message.len();
+    if len > MAX_CHECKED_PAYLOAD_LEN {
+        return false;
+    }
+    let Ok(key) = VerifyingKey::from_bytes(&pubkey.to_array()) else {
+        return false;
+    };
+    let mut buf = [0u8; MAX_CHECKED_PAYLOAD_LEN as usize];
+    message.copy_into_slice(&mut buf[..len as usize]);
+    key.verify_strict(
+        &buf[..len as usize],
+        &Signature::from_bytes(&signature.to_array()),
+    )
+    .is_ok()
+}
+
+/// The canonical bytes the admin signs to authorize a plain mint.
+fn mint_payload(
+    e: &Env,
+    user: &Address,
+    period: u64,
+    archetype: &Symbol,
+    data_hash: &BytesN<32>,
+) -> Bytes {
+    let mut payload = Bytes::new(e);
+    payload.append(&e.current_contract_address().to_xdr(e));
+    payload.append(&user.clone().to_xdr(e));
+    payload.append(&period.to_xdr(e));
+    payload.append(&archetype.clone().to_xdr(e));
+    payload.append(&data_hash.clone().to_xdr(e));
+    payload
+}
+
+/// Appends the optional terms a signature commits to, each as a `(tag, value)` XDR pair
+/// and only when set. A mint without terms signs exactly `mint_payload`, so existing
+/// signatures stay valid, while a signature made with terms can't be replayed without them.
+/// Terms appear in a fixed order: `algo` (when not sha256), `linked`, then `requires`.
+fn append_mint_terms(
+    e: &Env,
+    payload: &mut Bytes,
+    hash_algo: &Symbol,
+    linked_period: Option<u64>,
+    requires: Option<u64>,
+) {
+    if *hash_algo != DEFAULT_HASH_ALGO {
+        payload.append(&symbol_short!("algo").to_xdr(e));
+        payload.append(&hash_algo.clone().to_xdr(e));
+    }
+    if let Some(linked) = linked_period {
+        payload.append(&symbol_short!("linked").to_xdr(e));
+        payload.append(&linked.to_xdr(e));
+    }
+    if let Some(required) = requires {
+        payload.append(&symbol_short!("requires").to_xdr(e));
+        payload.append(&required.to_xdr(e));
+    }
+}
+
+/// Rejects the reserved all-time period and anything above the admin's `MaxPeriod` ceiling.
+fn check_period(e: &Env, period: u64) -> Result<(), ContractError> {
+    // Period 0 is the all-time slot, which only `update_alltime` writes
+    if period == ALL_TIME_PERIOD {
+        return Err(ContractError::InvalidPeriod);
+    }
+    let max_period: u64 = e.storage().instance().get(&DataKey::MaxPeriod).unwrap_or(0);
+    if max_period != 0 && period > max_period {
+        return Err(ContractError::PeriodTooHigh);
+    }
+    Ok(())
+}
+
+/// Checks the contract-wide rules a mint to `user` must pass, without writing anything.
+/// Returns the admin on success. Signatures and the per-ledger cap are checked by `mint`.
+fn check_mint_policy(e: &Env, user: &Address) -> Result<Address, ContractError> {
+    let instance = e.storage().instance();
+    if instance.get(&DataKey::Renounced).unwrap_or(false) {
+        return Err(ContractError::AdminRenounced);
+    }
+    let admin: Address = instance
+        .get(&DataKey::Admin)
+        .ok_or(ContractError::NotInitialized)?;
+
+    // Reject self-awarded wraps unless the admin opted in
+    if *user == admin && !instance.get(&DataKey::AllowSelfMint).unwrap_or(false) {
+        return Err(ContractError::SelfMintDisallowed);
+    }
+
+    // Closed cohorts only award wraps to pre-registered recipients
+    if instance.get(&DataKey::EligibilityEnforced).unwrap_or(false)
+        && !e
+            .storage()
+            .persistent()
+            .has(&DataKey::Eligible(user.clone()))
+    {
+        return Err(ContractError::NotEligible);
+    }
+
+    if instance.get(&DataKey::Paused).unwrap_or(false) {
+        return Err(ContractError::Paused);
+    }
+
+    // Hold mints until the announced start ledger (0 = always open)
+    let start: u32 = instance.get(&DataKey::MintStartLedger).unwrap_or(0);
+    if e.ledger().sequence() < start {
+        return Err(ContractError::MintNotStarted);
+    }
+
+    // Capped editions stop at MaxSupply outstanding wraps (0 = uncapped)
+    let max_supply: i128 = instance.get(&DataKey::MaxSupply).unwrap_or(0);
+    if max_supply > 0 && StellarWrapContract::total_supply(e.clone()) >= max_supply {
+        return Err(ContractError::MaxSupplyReached);
+    }
+    Ok(admin)
+}
+
+/// Checks the mint's authorization and the contract's mint policy, then records the wrap.
+/// Callers are responsible for authorizing the submitter first.
+fn mint(e: &Env, args: MintArgs) {
+    let MintArgs {
+        user,
+        period,
+        archetype,
+        data_hash,
+        hash_algo,
+        consented,
+        linked_period,
+        requires,
+        auth,
+    } = args;
+
+    check_period(e, period).unwrap_or_else(|err| panic_with_error!(e, err));
+
+    // 1. Verify initialization and the contract's mint policy
+    let admin = check_mint_policy(e, &user).unwrap_or_else(|err| panic_with_error!(e, err));
+
+    // Enforce the contract-wide per-ledger mint cap
+    consume_ledger_mint_allowance(e);
+
+    // Premium archetypes also need a signature from the second signing key
+    let requires_cosign: bool = e
+        .storage()
+        .persistent()
+        .get(&DataKey::RequiresCosign(archetype.clone()))
+        .unwrap_or(false);
+
+    let (issuer, signer_index) = match auth {
+        MintAuth::Signed {
+            signature,
+            key_index,
+            cosignature,
+        } => {
+            let admin_pubkey = signing_key(e, key_index)
+                .unwrap_or_else(|| panic_with_error!(e, ContractError::InvalidSignature));
+
+            // Never accept the same signature bytes twice, even if the wrap was revoked
+            let used_key =
+                DataKey::UsedSig(e.crypto().sha256(&signature.clone().into()).to_bytes());
+            if e.storage().persistent().has(&used_key) {
+                panic_with_error!(e, ContractError::SignatureReused);
+            }
+            e.storage().persistent().set(&used_key, &true);
+            e.storage()
+                .persistent()
+                .extend_ttl(&used_key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+
+            // 2. Reconstruct Payload
+            let mut payload = mint_payload(e, &user, period, &archetype, &data_hash);
+            append_mint_terms(e, &mut payload, &hash_algo, linked_period, requires);
+
+            // 3. Verify Admin Signature
+            e.crypto()
+                .ed25519_verify(&admin_pubkey, &payload, &signature);
+
+            if requires_cosign {
+                // The cosignature comes from the other live key
+                let cosigner = signing_keys(e).iter().find(|key| *key != admin_pubkey);
+                match (cosigner, cosignature) {
+                    (Some(cosigner), Some(cosignature)) => {
+                        e.crypto().ed25519_verify(&cosigner, &payload, &cosignature);
+                    }
+                    _ => panic_with_error!(e, ContractError::CosignRequired),
+                }
+            }
+            // Signed mints are attributed to the admin, who controls the signing keys
+            (admin, Some(key_index))
+        }
+        MintAuth::Minter(minter) => {
+            if requires_cosign {
+                panic_with_error!(e, ContractError::CosignRequired);
+            }
+            (minter, None)
+        }
+    };
+
+    // 4. Check Duplicates & Store Record (Switch to Persistent)
+    let wrap_key = DataKey::Wrap(user.clone(), period);
+    if e.storage().persistent().has(&wrap_key) {
+        panic_with_error!(e, ContractError::WrapAlreadyExists);
+    }
+
+    // A signed prerequisite must already be held
+    if let Some(required) = requires {
+        if !e
+            .storage()
+            .persistent()
+            .has(&DataKey::Wrap(user.clone(), required))
+        {
+            panic_with_error!(e, ContractError::PrerequisiteMissing);
+        }
+    }
+
+    // A link must point at another wrap the same user already holds
+    if let Some(linked) = linked_period {
+        if linked == period
+            || !e
+                .storage()
+                .persistent()
+                .has(&DataKey::Wrap(user.clone(), linked))
+        {
+            panic_with_error!(e, ContractError::LinkedPeriodMissing);
+        }
+    }
+
+    // With strict hashes on, a user may not reuse a data_hash across periods. Markers are
+    // only written while strict, so lenient mints stay as cheap as before
+    let hash_key = DataKey::UserHash(user.clone(), data_hash.clone());
+    let strict_hashes: bool = e
+        .storage()
+        .instance()
+        .get(&DataKey::StrictHashes)
+        .unwrap_or(false);
+    if strict_hashes && e.storage().persistent().has(&hash_key) {
+        panic_with_error!(e, ContractError::DuplicateHash);
+    }
+
+    let record = WrapRecord {
+        timestamp: e.ledger().timestamp(),
+        data_hash,
+        archetype: archetype.clone(),
+        period,
+        hash_algo,
+        ledger_seq: e.ledger().sequence(),
+        consented,
+        global_id: next_wrap_id(e),
+        linked_period,
+        minted_by: issuer.clone(),
+        signer_index,
+    };
+    insert_wrap(e, &user, &record);
+    if strict_hashes {
+        e.storage().persistent().set(&hash_key, &true);
+        e.storage()
+            .persistent()
+            .extend_ttl(&hash_key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+    }
+
+    // Attribute the mint to whoever authorized it
+    let minted_by_key = DataKey::MintedBy(issuer);
+    let minted_by: u32 = e.storage().persistent().get(&minted_by_key).unwrap_or(0);
+    let minted_by = minted_by
+        .checked_add(1)
+        .unwrap_or_else(|| panic_with_error!(e, ContractError::CountOverflow));
+    e.storage().persistent().set(&minted_by_key, &minted_by);
+    e.storage()
+        .persistent()
+        .extend_ttl(&minted_by_key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+
+    // 5. Emit Event
+    e.events().publish(
+        (event_prefix(e), symbol_short!("mint"), user, period),
+        (EVENT_SCHEMA, archetype),
+    );
+}
+
+#[contract]
+pub struct StellarWrapContract;
+
+#[contractimpl]
+impl StellarWrapContract {
+    /// Initialize with admin and the public key used to verify off-chain signatures.
+    pub fn initialize(e: Env, admin: Address, admin_pubkey: BytesN<32>) {
+        require_not_renounced(&e);
+        if e.storage().instance().has(&DataKey::Admin) {
+            panic_with_error!(e, ContractError::AlreadyInitialized);
+        }
+        // An all-zero key is a misconfiguration, not a usable signer
+        if admin_pubkey == BytesN::from_array(&e, &[0u8; 32]) {
+            panic_with_error!(e, ContractError::InvalidPubKey);
+        }
+        e.storage().instance().set(&DataKey::Admin, &admin);
+        e.storage().instance().set(
+            &DataKey::AdminPubKey,
+            &Vec::from_array(&e, [Some(admin_pubkey)]),
+        );
+        e.storage()
+            .instance()
+            .set(&DataKey::EventPrefix, &DEFAULT_EVENT_PREFIX);
+        // A fresh deployment starts on the current layout; `reset_config` keeps any older one
+        if !e.storage().instance().has(&DataKey::SchemaVersion) {
+            e.storage()
+                .instance()
+                .set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+        }
+    }
+
+    /// `initialize` for scripted deployments: fails with `WrongContract` unless this
+    /// contract's 32-byte id equals `expected_contract_id`.
+    pub fn initialize_checked(
+        e: Env,
+        admin: Address,
+        admin_pubkey: BytesN<32>,
+        expected_contract_id: BytesN<32>,
+    ) {
+        // A contract address encodes as a 4-byte ScVal tag, a 4-byte ScAddress tag, then the id
+        let xdr = e.current_contract_address().to_xdr(&e);
+        if xdr.slice(8..40) != Bytes::from(expected_contract_id) {
+            panic_with_error!(e, ContractError::WrongContract);
+        }
+        Self::initialize(e, admin, admin_pubkey);
+    }
+
+    /// Update the admin address. Only callable by the current admin.
+    pub fn update_admin(e: Env, new_admin: Address) {
+        require_admin(&e);
+        e.storage().instance().set(&DataKey::Admin, &new_admin);
+    }
+
+    /// Users claim their wrap using an Admin signature from the signing key in slot
+    /// `key_index`. `ed25519_verify` traps on failure, so the caller names the key instead
+    /// of the contract trying each one in turn.
+    ///
+    /// `hash_algo` tags how `data_hash` was computed and defaults to `sha256`. Any other
+    /// algorithm must be part of the signed payload (see `append_mint_terms`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_wrap(
+        e: Env,
+        user: Address,
+        period: u64,
+        archetype: Symbol,
+        data_hash: BytesN<32>,
+        signature: BytesN<64>,
+        key_index: u32,
+        hash_algo: Option<Symbol>,
+    ) {
+        // Security: Ensure the user actually signed this transaction
+        user.require_auth();
+        let hash_algo = hash_algo.unwrap_or(DEFAULT_HASH_ALGO);
+        mint(
+            &e,
+            MintArgs {
+                user,
+                period,
+                archetype,
+                data_hash,
+                hash_algo,
+                consented: false,
+                linked_period: None,
+                requires: None,
+                auth: MintAuth::Signed {
+                    signature,
+                    key_index,
+                    cosignature: None,
+                },
+            },
+        );
+    }
+
+    /// `mint_wrap` for archetypes flagged with `set_requires_cosign`: `signature` must
+    /// come from the key in slot `key_index` and `cosignature` from the other live key.
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_wrap_cosigned(
+        e: Env,
+        user: Address,
+        period: u64,
+        archetype: Symbol,
+        data_hash: BytesN<32>,
+        signature: BytesN<64>,
+        key_index: u32,
+        cosignature: BytesN<64>,
+    ) {
+        user.require_auth();
+        mint(
+            &e,
+            MintArgs {
+                user,
+                period,
+                archetype,
+                data_hash,
+                hash_algo: DEFAULT_HASH_ALGO,
+                consented: false,
+                linked_period: None,
+                requires: None,
+                auth: MintAuth::Signed {
+                    signature,
+                    key_index,
+                    cosignature: Some(cosignature),
+                },
+            },
+        );
+    }
+
+    /// `mint_wrap` that also needs the recipient's acknowledgment: `recipient_signature`
+    /// must be the user's account key signing `wrap_id(user, period)`. The record is
+    /// stored with `consented = true`. A consent signature that does not verify, or a
+    /// contract address that has no key to consent with, fails with `ConsentInvalid`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_wrap_with_consent(
+        e: Env,
+        user: Address,
+        period: u64,
+        archetype: Symbol,
+        data_hash: BytesN<32>,
+        signature: BytesN<64>,
+        key_index: u32,
+        recipient_signature: BytesN<64>,
+    ) {
+        user.require_auth();
+        let recipient_key = account_pubkey(&e, &user)
+            .unwrap_or_else(|| panic_with_error!(e, ContractError::ConsentInvalid));
+        let wrap_id = Self::wrap_id(e.clone(), user.clone(), period);
+        if !signature_valid(&recipient_key, &wrap_id.into(), &recipient_signature) {
+            panic_with_error!(e, ContractError::ConsentInvalid);
+        }
+        mint(
+            &e,
+            MintArgs {
+                user,
+                period,
+                archetype,
+                data_hash,
+                hash_algo: DEFAULT_HASH_ALGO,
+                consented: true,
+                linked_period: None,
+                requires: None,
+                auth: MintAuth::Signed {
+                    signature,
+                    key_index,
+                    cosignature: None,
+                },
+            },
+        );
+    }
+
+    /// `mint_wrap` for a wrap that corrects or continues the user's `linked_period`, which
+    /// must be another wrap they already hold (else `LinkedPeriodMissing`). The link is part
+    /// of the signed payload (see `append_mint_terms`), so only the admin chooses it. `None`
+    /// behaves exactly like `mint_wrap`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_wrap_linked(
+        e: Env,
+        user: Address,
+        period: u64,
+        archetype: Symbol,
+        data_hash: BytesN<32>,
+        signature: BytesN<64>,
+        key_index: u32,
+        linked_period: Option<u64>,
+    ) {
+        user.require_auth();
+        mint(
+            &e,
+            MintArgs {
+                user,
+                period,
+                archetype,
+                data_hash,
+                hash_algo: DEFAULT_HASH_ALGO,
+                consented: false,
+                linked_period,
+                requires: None,
+                auth: MintAuth::Signed {
+                    signature,
+                    key_index,
+                    cosignature: None,
+                },
+            },
+        );
+    }
+
+    /// `mint_wrap` that only succeeds if the user already holds the `requires` period,
+    /// e.g. for streaks. The prerequisite is part of the signed payload (see
+    /// `append_mint_terms`), so the signature can't be redeemed through `mint_wrap`
+    /// without it. `None` behaves exactly like `mint_wrap`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_wrap_conditional(
+        e: Env,
+        user: Address,
+        period: u64,
+        archetype: Symbol,
+        data_hash: BytesN<32>,
+        signature: BytesN<64>,
+        key_index: u32,
+        requires: Option<u64>,
+    ) {
+        user.require_auth();
+        mint(
+            &e,
+            MintArgs {
+                user,
+                period,
+                archetype,
+                data_hash,
+                hash_algo: DEFAULT_HASH_ALGO,
+                consented: false,
+                linked_period: None,
+                requires,
+                auth: MintAuth::Signed {
+                    signature,
+                    key_index,
+                    cosignature: None,
+                },
+            },
+        );
+    }
+
+    /// Mint directly as the admin or the designated minter, without an off-chain
+    /// signature. `minter` must authorize the call.
+    pub fn mint_by(
+        e: Env,
+        minter: Address,
+        user: Address,
+        period: u64,
+        archetype: Symbol,
+        data_hash: BytesN<32>,
+    ) {
+        minter.require_auth();
+        let admin: Option<Address> = e.storage().instance().get(&DataKey::Admin);
+        let designated: Option<Address> = e.storage().instance().get(&DataKey::Minter);
+        if admin.as_ref() != Some(&minter) && designated.as_ref() != Some(&minter) {
+            panic_with_error!(e, ContractError::Unauthorized);
+        }
+        mint(
+            &e,
+            MintArgs {
+                user,
+                period,
+                archetype,
+                data_hash,
+                hash_algo: DEFAULT_HASH_ALGO,
+                consented: false,
+                linked_period: None,
+                requires: None,
+                auth: MintAuth::Minter(minter),
+            },
+        );
+    }
+
+    /// Dry-runs `mint_wrap_batch`, returning each entry's outcome in order without writing.
+    /// Entries are checked against the current state plus the entries accepted before them,
+    /// so the ledger mint cap, max supply, per-user period limit, strict hashes and reused
+    /// signatures all see the earlier mints. A signature that doesn't verify against its key
+    /// slot reports `InvalidSignature`. Checking signatures in contract code is costly, so
+    /// call this from simulation rather than in a submitted transaction.
+    pub fn preflight_batch(e: Env, entries: Vec<MintEntry>) -> Vec<Result<(), ContractError>> {
+        if entries.len() > MAX_BATCH {
+            panic_with_error!(e, ContractError::BatchTooLarge);
+        }
+
+        let instance = e.storage().instance();
+        let max_supply: i128 = instance.get(&DataKey::MaxSupply).unwrap_or(0);
+        let strict_hashes: bool = instance.get(&DataKey::StrictHashes).unwrap_or(false);
+        let (ledger_cap, ledger_used) = ledger_mint_usage(&e);
+        let supply = Self::total_supply(e.clone());
+
+        let mut results = Vec::new(&e);
+        let mut accepted: Vec<MintEntry> = Vec::new(&e);
+        for entry in entries.iter() {
+            let result = check_mint_policy(&e, &entry.user).and_then(|_| {
+                check_period(&e, entry.period)?;
+                let pending = accepted.len();
+                if max_supply > 0 && supply + pending as i128 >= max_supply {
+                    return Err(ContractError::MaxSupplyReached);
+                }
+                if ledger_cap != 0 && ledger_used.saturating_add(pending) >= ledger_cap {
+                    return Err(ContractError::GlobalRateExceeded);
+                }
+                let requires_cosign: bool = e
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::RequiresCosign(entry.archetype.clone()))
+                    .unwrap_or(false);
+                if requires_cosign {
+                    return Err(ContractError::CosignRequired);
+                }
+                let Some(pubkey) = signing_key(&e, entry.key_index) else {
+                    return Err(ContractError::InvalidSignature);
+                };
+                let digest = e
+                    .crypto()
+                    .sha256(&entry.signature.clone().into())
+                    .to_bytes();
+                if e.storage().persistent().has(&DataKey::UsedSig(digest))
+                    || accepted
+                        .iter()
+                        .any(|prior| prior.signature == entry.signature)
+                {
+                    return Err(ContractError::SignatureReused);
+                }
+                let payload = mint_payload(
+                    &e,
+                    &entry.user,
+                    entry.period,
+                    &entry.archetype,
+                    &entry.data_hash,
+                );
+                if !signature_valid(&pubkey, &payload, &entry.signature) {
+                    return Err(ContractError::InvalidSignature);
+                }
+                if e.storage()
+                    .persistent()
+                    .has(&DataKey::Wrap(entry.user.clone(), entry.period))
+                    || accepted
+                        .iter()
+                        .any(|prior| prior.user == entry.user && prior.period == entry.period)
+                {
+                    return Err(ContractError::WrapAlreadyExists);
+                }
+                if strict_hashes
+                    && (e.storage().persistent().has(&DataKey::UserHash(
+                        entry.user.clone(),
+                        entry.data_hash.clone(),
+                    )) || accepted.iter().any(|prior| {
+                        prior.user == entry.user && prior.data_hash == entry.data_hash
+                    }))
+                {
+                    return Err(ContractError::DuplicateHash);
+                }
+                let held = Self::list_periods(e.clone(), entry.user.clone()).len();
+                let pending_for_user = accepted
+                    .iter()
+                    .filter(|prior| prior.user == entry.user)
+                    .count() as u32;
+                if held + pending_for_user >= max_periods_per_user(&e) {
+                    return Err(ContractError::UserPeriodLimit);
+                }
+                Ok(())
+            });
+            if result.is_ok() {
+                accepted.push_back(entry);
+            }
+            results.push_back(result);
+        }
+        results
+    }
+
+    /// Retry-safe `mint_wrap`: if the wrap already exists with the same `data_hash`
+    /// this is a no-op, and only a differing hash fails with `WrapAlreadyExists`.
+    pub fn mint_wrap_idempotent(
+        e: Env,
+        user: Address,
+        period: u64,
+        archetype: Symbol,
+        data_hash: BytesN<32>,
+        signature: BytesN<64>,
+        key_index: u32,
+    ) -> Result<(), ContractError> {
+        user.require_auth();
+        if let Some(existing) = load_wrap(&e, &user, period) {
+            return if existing.data_hash == data_hash {
+                Ok(())
+            } else {
+                Err(ContractError::WrapAlreadyExists)
+            };
+        }
+        mint(
+            &e,
+            MintArgs {
+                user,
+                period,
+                archetype,
+                data_hash,
+                hash_algo: DEFAULT_HASH_ALGO,
+                consented: false,
+                linked_period: None,
+                requires: None,
+                auth: MintAuth::Signed {
+                    signature,
+                    key_index,
+                    cosignature: None,
+                },
+            },
+        );
+        Ok(())
+    }
+
+    /// Admin backfill: mint several signed wraps in one transaction. Each entry still
+    /// needs a valid admin signature, and the whole batch fails if any entry does.
+    pub fn mint_wrap_batch(e: Env, records: Vec<MintEntry>) {
+        require_minting_admin(&e);
+        if records.len() > MAX_BATCH {
+            panic_with_error!(e, ContractError::BatchTooLarge);
+        }
+        for entry in records.iter() {
+            mint(
+                &e,
+                MintArgs {
+                    user: entry.user,
+                    period: entry.period,
+                    archetype: entry.archetype,
+                    data_hash: entry.data_hash,
+                    hash_algo: DEFAULT_HASH_ALGO,
+                    consented: false,
+                    linked_period: None,
+                    requires: None,
+                    auth: MintAuth::Signed {
+                        signature: entry.signature,
+                        key_index: entry.key_index,
+                        cosignature: None,
+                    },
+                },
+            );
+        }
+    }
+
+    /// `mint_wrap` for integrations that still send periods as symbols like `2024_01`.
+    /// The admin signature must cover the canonical numeric period.
+    pub fn mint_wrap_sym(
+        e: Env,
+        user: Address,
+        period_sym: Symbol,
+        archetype: Symbol,
+        data_hash: BytesN<32>,
+        signature: BytesN<64>,
+        key_index: u32,
+    ) {
+        // Security: Ensure the user actually signed this transaction
+        user.require_auth();
+        let period = parse_period_symbol(&e, &period_sym);
+        mint(
+            &e,
+            MintArgs {
+                user,
+                period,
+                archetype,
+                data_hash,
+                hash_algo: DEFAULT_HASH_ALGO,
+                consented: false,
+                linked_period: None,
+                requires: None,
+                auth: MintAuth::Signed {
+                    signature,
+                    key_index,
+                    cosignature: None,
+                },
+            },
+        );
+    }
+
+    /// Self-service mint: the user submits (and pays for) a wrap the admin pre-authorized
+    /// off-chain by signing its payload. Only the user's auth is required on-chain.
+    pub fn claim_wrap(
+        e: Env,
+        user: Address,
+        period: u64,
+        archetype: Symbol,
+        data_hash: BytesN<32>,
+        admin_signature: BytesN<64>,
+        key_index: u32,
+    ) {
+        // Security: Ensure the user actually signed this transaction
+        user.require_auth();
+        charge_mint_fee(&e, &user);
+        mint(
+            &e,
+            MintArgs {
+                user,
+                period,
+                archetype,
+                data_hash,
+                hash_algo: DEFAULT_HASH_ALGO,
+                consented: false,
+                linked_period: None,
+                requires: None,
+                auth: MintAuth::Signed {
+                    signature: admin_signature,
+                    key_index,
+                    cosignature: None,
+                },
+            },
+        );
+    }
+
+    /// Create or overwrite the user's all-time wrap stored at `ALL_TIME_PERIOD`. Admin only.
+    ///
+    /// Unlike regular periods, the all-time wrap stays mutable so it can track a
+    /// user's lifetime persona. Creating it is a mint and must pass the mint policy and the
+    /// per-ledger cap; overwriting an existing one is not.
+    pub fn update_alltime(e: Env, user: Address, archetype: Symbol, data_hash: BytesN<32>) {
+        let admin = require_admin(&e);
+
+        let wrap_key = DataKey::Wrap(user.clone(), ALL_TIME_PERIOD);
+        let previous = load_wrap(&e, &user, ALL_TIME_PERIOD);
+        if previous.is_none() {
+            check_mint_policy(&e, &user).unwrap_or_else(|err| panic_with_error!(e, err));
+            consume_ledger_mint_allowance(&e);
+        }
+        let record = WrapRecord {
+            timestamp: e.ledger().timestamp(),
+            data_hash,
+            archetype: archetype.clone(),
+            period: ALL_TIME_PERIOD,
+            hash_algo: DEFAULT_HASH_ALGO,
+            ledger_seq: e.ledger().sequence(),
+            consented: false,
+            // Overwrites keep the id the all-time wrap was first minted with
+            global_id: previous
+                .as_ref()
+                .map_or_else(|| next_wrap_id(&e), |previous| previous.global_id),
+            linked_period: None,
+            minted_by: admin,
+            signer_index: None,
+        };
+        if let Some(previous) = previous {
+            shift_archetype_count(&e, &previous.archetype, 0, 1);
+            shift_archetype_count(&e, &archetype, 1, 0);
+            note_archetype(&e, &archetype);
+            e.storage().persistent().set(&wrap_key, &record);
+            e.storage()
+                .persistent()
+                .extend_ttl(&wrap_key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+        } else {
+            insert_wrap(&e, &user, &record);
+        }
+
+        e.events().publish(
+            (event_prefix(&e), symbol_short!("alltime"), user),
+            (EVENT_SCHEMA, archetype),
+        );
+    }
+
+    /// Rename `old` to `new` on each listed wrap that carries `old`, moving the archetype
+    /// tallies along. The caller supplies the affected wraps since records can't be
+    /// scanned. Returns how many were rewritten. At most `MAX_BATCH` entries. Admin only.
+    pub fn rename_archetype(
+        e: Env,
+        old: Symbol,
+        new: Symbol,
+        users_periods: Vec<(Address, u64)>,
+    ) -> u32 {
+        require_admin(&e);
+        if users_periods.len() > MAX_BATCH {
+            panic_with_error!(e, ContractError::BatchTooLarge);
+        }
+
+        let mut renamed = 0;
+        for (user, period) in users_periods.iter() {
+            let Some(mut record) = load_wrap(&e, &user, period) else {
+                continue;
+            };
+            let wrap_key = DataKey::Wrap(user, period);
+            if record.archetype != old {
+                continue;
+            }
+            record.archetype = new.clone();
+            e.storage().persistent().set(&wrap_key, &record);
+            renamed += 1;
+        }
+
+        if renamed > 0 {
+            shift_archetype_count(&e, &old, 0, renamed);
+            shift_archetype_count(&e, &new, renamed, 0);
+            note_archetype(&e, &new);
+        }
+        renamed
+    }
+
+    /// Attach a custom `key = value` attribute to an existing wrap, e.g. `rank = gold`,
+    /// overwriting any previous value. Fails with `WrapNotFound` if the wrap does not
+    /// exist. Admin only.
+    pub fn set_attribute(e: Env, user: Address, period: u64, key: Symbol, value: Symbol) {
+        require_admin(&e);
+        if !e
+            .storage()
+            .persistent()
+            .has(&DataKey::Wrap(user.clone(), period))
+        {
+            panic_with_error!(e, ContractError::WrapNotFound);
+        }
+        let attr_key = DataKey::Attr(user, period, key);
+        e.storage().persistent().set(&attr_key, &value);
+        e.storage()
+            .persistent()
+            .extend_ttl(&attr_key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+    }
+
+    /// Move every wrap held by `from` to `to`, e.g. after a wallet change. Fails with
+    /// `WrapAlreadyExists` if `to` already holds any of the periods, or `UserPeriodLimit` if
+    /// `to` would end up over the per-user period limit. Acknowledgments move with the
+    /// wraps; custom attributes stay behind. Returns how many wraps moved. Admin only.
+    pub fn migrate_wraps(e: Env, from: Address, to: Address) -> u32 {
+        require_admin(&e);
+        let periods = Self::list_periods(e.clone(), from.clone());
+        let to_held = Self::list_periods(e.clone(), to.clone()).len();
+        // The merged index must stay within the bound that keeps index walks affordable
+        if to_held + periods.len() > max_periods_per_user(&e) {
+            panic_with_error!(e, ContractError::UserPeriodLimit);
+        }
+        for period in periods.iter() {
+            if e.storage()
+                .persistent()
+                .has(&DataKey::Wrap(to.clone(), period))
+            {
+                panic_with_error!(e, ContractError::WrapAlreadyExists);
+            }
+        }
+
+        let mut moved: u32 = 0;
+        for period in periods.iter() {
+            let from_key = DataKey::Wrap(from.clone(), period);
+            let Some(record) = load_wrap(&e, &from, period) else {
+                continue;
+            };
+            e.storage().persistent().remove(&from_key);
+            let to_key = DataKey::Wrap(to.clone(), period);
+            e.storage().persistent().set(&to_key, &record);
+            e.storage()
+                .persistent()
+                .extend_ttl(&to_key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+            index_period(&e, &to, period);
+
+            let from_hash_key = DataKey::UserHash(from.clone(), record.data_hash.clone());
+            if e.storage().persistent().has(&from_hash_key) {
+                e.storage().persistent().remove(&from_hash_key);
+                let to_hash_key = DataKey::UserHash(to.clone(), record.data_hash);
+                e.storage().persistent().set(&to_hash_key, &true);
+                e.storage()
+                    .persistent()
+                    .extend_ttl(&to_hash_key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+            }
+
+            let from_ack_key = DataKey::Acked(from.clone(), period);
+            if e.storage().persistent().has(&from_ack_key) {
+                e.storage().persistent().remove(&from_ack_key);
+                let to_ack_key = DataKey::Acked(to.clone(), period);
+                e.storage().persistent().set(&to_ack_key, &true);
+                e.storage()
+                    .persistent()
+                    .extend_ttl(&to_ack_key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+            }
+            moved += 1;
+        }
+        e.storage()
+            .persistent()
+            .remove(&DataKey::UserPeriods(from.clone()));
+
+        let from_count_key = DataKey::WrapCount(from.clone());
+        let from_count: u32 = e.storage().persistent().get(&from_count_key).unwrap_or(0);
+        e.storage().persistent().remove(&from_count_key);
+        let to_count_key = DataKey::WrapCount(to.clone());
+        let to_count: u32 = e.storage().persistent().get(&to_count_key).unwrap_or(0);
+        let new_count = to_count
+            .checked_add(from_count)
+            .unwrap_or_else(|| panic_with_error!(e, ContractError::CountOverflow));
+        e.storage().persistent().set(&to_count_key, &new_count);
+        e.storage()
+            .persistent()
+            .extend_ttl(&to_count_key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+
+        if !periods.is_empty() && to_held > 0 {
+            decrement_global(&e, &DataKey::HolderCount);
+        }
+        update_leaderboard(&e, &from, 0);
+        update_leaderboard(&e, &to, new_count);
+
+        e.events().publish(
+            (event_prefix(&e), symbol_short!("migrate"), from, to),
+            (EVENT_SCHEMA, moved),
+        );
+        moved
+    }
+
+    /// Re-publish the mint event for an existing wrap, for indexers that missed it. The
+    /// topics match the original event and the data is `(schema, archetype, true)`, the flag
+    /// marking a replay. Fails with `WrapNotFound` if the wrap does not exist. Admin only.
+    pub fn reemit_mint(e: Env, user: Address, period: u64) {
+        require_admin(&e);
+
+        let record = load_wrap(&e, &user, period)
+            .unwrap_or_else(|| panic_with_error!(e, ContractError::WrapNotFound));
+
+        e.events().publish(
+            (event_prefix(&e), symbol_short!("mint"), user, record.period),
+            (EVENT_SCHEMA, record.archetype, true),
+        );
+    }
+
+    /// Recompute the user's `WrapCount` from the records in their period index. Wraps minted
+    /// before the index existed only count once `backfill_index` has added them. Admin only.
+    pub fn recount_user(e: Env, user: Address) -> u32 {
+        require_admin(&e);
+
+        let mut count: u32 = 0;
+        for period in Self::list_periods(e.clone(), user.clone()).iter() {
+            if e.storage()
+                .persistent()
+                .has(&DataKey::Wrap(user.clone(), period))
+            {
+                count += 1;
+            }
+        }
+
+        let count_key = DataKey::WrapCount(user.clone());
+        e.storage().persistent().set(&count_key, &count);
+        e.storage()
+            .persistent()
+            .extend_ttl(&count_key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+        update_leaderboard(&e, &user, count);
+
+        e.events().publish(
+            (event_prefix(&e), symbol_short!("recount"), user),
+            (EVENT_SCHEMA, count),
+        );
+        count
+    }
+
+    /// Revoke a single wrap, dropping its period from the user's index without
+    /// disturbing the order of the rest. Returns false if the user did not hold it. Admin only.
+    pub fn revoke_wrap(e: Env, user: Address, period: u64) -> bool {
+        require_admin(&e);
+        revoke(&e, &user, period)
+    }
+
+    /// Revoke `period` from each listed user who holds it, e.g. after its data source was
+    /// invalidated. Returns how many wraps were revoked. Admin only.
+    pub fn revoke_period(e: Env, period: u64, users: Vec<Address>) -> u32 {
+        require_admin(&e);
+        if users.len() > MAX_BATCH {
+            panic_with_error!(e, ContractError::BatchTooLarge);
+        }
+
+        let mut revoked = 0;
+        for user in users.iter() {
+            if revoke(&e, &user, period) {
+                revoked += 1;
+            }
+        }
+        revoked
+    }
+
+    /// Register an additional signing key, e.g. the next key during a rotation. It takes
+    /// the next unused slot (see `signing_key_slots`). Admin only.
+    pub fn add_signing_key(e: Env, pubkey: BytesN<32>) {
+        require_admin(&e);
+        if pubkey == BytesN::from_array(&e, &[0u8; 32]) {
+            panic_with_error!(e, ContractError::InvalidPubKey);
+        }
+        let keys = signing_keys(&e);
+        if keys.len() >= MAX_SIGNING_KEYS || keys.contains(&pubkey) {
+            panic_with_error!(e, ContractError::InvalidSignerSet);
+        }
+        let mut slots = signing_key_slots(&e);
+        slots.push_back(Some(pubkey));
+        e.storage().instance().set(&DataKey::AdminPubKey, &slots);
+    }
+
+    /// Stop accepting signatures from `pubkey`. Its slot is left empty rather than reused, so
+    /// the other keys keep their indexes. The last remaining key cannot be removed. Admin only.
+    pub fn remove_signing_key(e: Env, pubkey: BytesN<32>) {
+        require_admin(&e);
+        let mut slots = signing_key_slots(&e);
+        let index = slots
+            .first_index_of(Some(pubkey))
+            .unwrap_or_else(|| panic_with_error!(e, ContractError::SignerNotFound));
+        if signing_keys(&e).len() == 1 {
+            panic_with_error!(e, ContractError::InvalidSignerSet);
+        }
+        slots.set(index, None);
+        e.storage().instance().set(&DataKey::AdminPubKey, &slots);
+    }
+
+    /// Set the base URI that `token_uri` prefixes to a wrap's data hash. Admin only.
+    pub fn set_base_uri(e: Env, base: String) {
+        require_admin(&e);
+        if base.len() > MAX_BASE_URI_LEN {
+            panic_with_error!(e, ContractError::UriTooLong);
+        }
+        e.storage().instance().set(&DataKey::BaseUri, &base);
+    }
+
+    /// Charge `amount` of `fee_token`, paid to the admin, on every `claim_wrap`; 0 disables
+    /// the fee. Admin only.
+    pub fn set_mint_fee(e: Env, fee_token: Address, amount: i128) {
+        require_admin(&e);
+        if amount < 0 {
+            panic_with_error!(e, ContractError::InvalidFee);
+        }
+        e.storage().instance().set(&DataKey::FeeToken, &fee_token);
+        e.storage().instance().set(&DataKey::FeeAmount, &amount);
+    }
+
+    /// Expire wraps `seconds` after minting so they stop counting toward `balance_of`;
+    /// 0 means wraps never expire. Admin only.
+    pub fn set_wrap_lifetime(e: Env, seconds: u64) {
+        require_admin(&e);
+        e.storage().instance().set(&DataKey::WrapLifetime, &seconds);
+    }
+
+    /// Register `user` as an eligible mint recipient. Admin only.
+    pub fn add_eligible(e: Env, user: Address) {
+        require_admin(&e);
+        let key = DataKey::Eligible(user);
+        e.storage().persistent().set(&key, &true);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+    }
+
+    /// Remove `user` from the eligible recipients. Admin only.
+    pub fn remove_eligible(e: Env, user: Address) {
+        require_admin(&e);
+        e.storage().persistent().remove(&DataKey::Eligible(user));
+    }
+
+    /// Limit mints to eligible recipients, failing others with `NotEligible`. Admin only.
+    pub fn set_eligibility_enforced(e: Env, enforced: bool) {
+        require_admin(&e);
+        e.storage()
+            .instance()
+            .set(&DataKey::EligibilityEnforced, &enforced);
+    }
+
+    /// Set the wrap counts at which a holder's mint emits a `milestn` event. Admin only.
+    pub fn set_milestones(e: Env, milestones: Vec<u32>) {
+        require_admin(&e);
+        e.storage()
+            .instance()
+            .set(&DataKey::Milestones, &milestones);
+    }
+
+    /// Set how much a wrap of `archetype` adds to `weighted_score`. Admin only.
+    pub fn set_archetype_weight(e: Env, archetype: Symbol, weight: u32) {
+        require_admin(&e);
+        let key = DataKey::ArchetypeWeight(archetype);
+        e.storage().persistent().set(&key, &weight);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+    }
+
+    /// Set the display label for `archetype`, e.g. "The Architect". Admin only.
+    pub fn set_archetype_label(e: Env, archetype: Symbol, label: String) {
+        require_admin(&e);
+        let key = DataKey::ArchetypeLabel(archetype);
+        e.storage().persistent().set(&key, &label);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+    }
+
+    /// Set the registry description shown by wallets. Admin only.
+    pub fn set_description(e: Env, description: String) {
+        require_admin(&e);
+        e.storage()
+            .instance()
+            .set(&DataKey::Description, &description);
+    }
+
+    /// Set the token symbol reported by `symbol()`, e.g. after a rebrand. Admin only.
+    pub fn set_symbol(e: Env, new_symbol: String) {
+        require_admin(&e);
+        e.storage()
+            .instance()
+            .set(&DataKey::TokenSymbol, &new_symbol);
+    }
+
+    /// Replace the contract code with an uploaded wasm. Admin only.
+    ///
+    /// Emits `upgrade` with the new wasm hash and the bumped code version first, so
+    /// indexers know to invalidate their caches.
+    pub fn upgrade(e: Env, new_wasm_hash: BytesN<32>) {
+        require_admin(&e);
+        let version: u32 = e
+            .storage()
+            .instance()
+            .get(&DataKey::CodeVersion)
+            .unwrap_or(0)
+            + 1;
+        e.storage().instance().set(&DataKey::CodeVersion, &version);
+        e.events().publish(
+            (event_prefix(&e), symbol_short!("upgrade")),
+            (EVENT_SCHEMA, new_wasm_hash.clone(), version),
+        );
+        e.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Rewrites a page of v1 records keyed by `(user, Symbol)` onto the numeric
+    /// `(user, u64)` keys, e.g. `2024_01` becomes `202401`. Call it with pages of at most
+    /// `MAX_BATCH` entries, then `finalize_migration` once every record has moved. Admin
+    /// only; fails with `AlreadyMigrated` once the schema is at v2. Entries with no legacy
+    /// record are skipped, and so are entries whose numeric record already exists, leaving
+    /// both records in place.
+    /// `WrapCount` is not bumped since v1 already counted these wraps, but the contract-wide
+    /// stats, leaderboard and period index pick them up. Returns how many moved.
+    pub fn migrate_v1_to_v2(e: Env, entries: Vec<(Address, Symbol)>) -> u32 {
+        let admin = require_admin(&e);
+        require_unmigrated(&e);
+        if entries.len() > MAX_BATCH {
+            panic_with_error!(e, ContractError::BatchTooLarge);
+        }
+
+        let mut migrated = 0;
+        for (user, period_sym) in entries.iter() {
+            let legacy_key = LegacyDataKey::Wrap(user.clone(), period_sym.clone());
+            let Some(legacy) = e
+                .storage()
+                .persistent()
+                .get::<_, LegacyWrapRecord>(&legacy_key)
+            else {
+                continue;
+            };
+            // Keep both records when the numeric one exists, so WrapCount still matches them
+            let period = parse_period_symbol(&e, &period_sym);
+            let wrap_key = DataKey::Wrap(user.clone(), period);
+            if e.storage().persistent().has(&wrap_key) {
+                continue;
+            }
+            e.storage().persistent().remove(&legacy_key);
+            let record = WrapRecord {
+                timestamp: legacy.timestamp,
+                data_hash: legacy.data_hash,
+                archetype: legacy.archetype,
+                period,
+                hash_algo: DEFAULT_HASH_ALGO,
+                // v1 never recorded the mint ledger
+                ledger_seq: 0,
+                consented: false,
+                global_id: next_wrap_id(&e),
+                linked_period: None,
+                // v1 never recorded who minted; the admin was the only issuer
+                minted_by: admin.clone(),
+                signer_index: None,
+            };
+            e.storage().persistent().set(&wrap_key, &record);
+            e.storage()
+                .persistent()
+                .extend_ttl(&wrap_key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+
+            adopt_wrap(&e, &user, &record);
+            migrated += 1;
+        }
+        migrated
+    }
+
+    /// Adds wraps stored before the period index existed to their holder's `UserPeriods`,
+    /// in pages of at most `MAX_BATCH` `(user, period)` entries. Entries with no stored wrap,
+    /// or that are already indexed, are skipped. `WrapCount` is left alone since those wraps
+    /// were counted when minted, but supply, holders, distinct periods, archetype counts and
+    /// the leaderboard pick them up. Admin only. Returns how many were indexed.
+    pub fn backfill_index(e: Env, entries: Vec<(Address, u64)>) -> u32 {
+        require_admin(&e);
+        if entries.len() > MAX_BATCH {
+            panic_with_error!(e, ContractError::BatchTooLarge);
+        }
+
+        let mut indexed = 0;
+        for (user, period) in entries.iter() {
+            let Some(record) = load_wrap(&e, &user, period) else {
+                continue;
+            };
+            if Self::list_periods(e.clone(), user.clone())
+                .binary_search(period)
+                .is_ok()
+            {
+                continue;
+            }
+            adopt_wrap(&e, &user, &record);
+            indexed += 1;
+        }
+        indexed
+    }
+
+    /// Marks the storage layout as v2 once every `migrate_v1_to_v2` page has run; both then
+    /// fail with `AlreadyMigrated`. Admin only.
+    pub fn finalize_migration(e: Env) {
+        require_admin(&e);
+        require_unmigrated(&e);
+        e.storage()
+            .instance()
+            .set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+    }
+
+    /// Permanently freeze the configuration: admin changes, signing key changes, upgrades
+    /// and every setter fail with `ControlsRenounced` from then on, while minting with the
+    /// existing signers and all reads keep working. This cannot be undone. Admin only.
+    pub fn renounce_admin_controls(e: Env) {
+        require_admin(&e);
+        e.storage()
+            .instance()
+            .set(&DataKey::ControlsRenounced, &true);
+    }
+
+    /// Permanently give up admin control: the admin is deleted and minting, upgrades
+    /// and every admin setter fail with `AdminRenounced` from then on. Reads keep
+    /// working. This cannot be undone.
+    pub fn renounce_admin(e: Env) {
+        require_admin(&e);
+        e.storage().instance().remove(&DataKey::Admin);
+        e.storage().instance().set(&DataKey::Renounced, &true);
+    }
+
+    /// Emergency reset: remove the admin, signing keys and every config setting while
+    /// leaving wrap records, counts and indices untouched. The contract can then be
+    /// initialized again. Admin only.
+    pub fn reset_config(e: Env) {
+        require_admin(&e);
+        let instance = e.storage().instance();
+        instance.remove(&DataKey::Admin);
+        instance.remove(&DataKey::AdminPubKey);
+        instance.remove(&DataKey::Minter);
+        instance.remove(&DataKey::PendingMinter);
+        instance.remove(&DataKey::BaseUri);
+        instance.remove(&DataKey::Description);
+        instance.remove(&DataKey::TokenSymbol);
+        instance.remove(&DataKey::FeeToken);
+        instance.remove(&DataKey::FeeAmount);
+        instance.remove(&DataKey::WrapLifetime);
+        instance.remove(&DataKey::Milestones);
+        instance.remove(&DataKey::EligibilityEnforced);
+        instance.remove(&DataKey::AllowSelfMint);
+        instance.remove(&DataKey::AllowSelfBurn);
+        instance.remove(&DataKey::EventPrefix);
+        instance.remove(&DataKey::LedgerMintCap);
+        instance.remove(&DataKey::LedgerMintCount);
+        instance.remove(&DataKey::MintStartLedger);
+        instance.remove(&DataKey::Paused);
+        instance.remove(&DataKey::MaxSupply);
+        instance.remove(&DataKey::PrivateMode);
+        instance.remove(&DataKey::StrictHashes);
+        instance.remove(&DataKey::MaxPeriodsPerUser);
+        instance.remove(&DataKey::MaxPeriod);
+        // Pin the layout version so initializing again can't mark unmigrated data as v2
+        let version: u32 = instance.get(&DataKey::SchemaVersion).unwrap_or(1);
+        instance.set(&DataKey::SchemaVersion, &version);
+    }
+
+    /// Allow or forbid minting wraps to the admin's own address. Admin only.
+    pub fn set_allow_self_mint(e: Env, allowed: bool) {
+        require_admin(&e);
+        e.storage()
+            .instance()
+            .set(&DataKey::AllowSelfMint, &allowed);
+    }
+
+    /// Change the namespace topic prepended to emitted events. Admin only.
+    pub fn set_event_prefix(e: Env, prefix: Symbol) {
+        require_admin(&e);
+        e.storage().instance().set(&DataKey::EventPrefix, &prefix);
+    }
+
+    /// Limit how many wraps may be minted per ledger across the contract; 0 disables the cap. Admin only.
+    pub fn set_ledger_mint_cap(e: Env, cap: u32) {
+        require_admin(&e);
+        e.storage().instance().set(&DataKey::LedgerMintCap, &cap);
+    }
+
+    /// Designate the address allowed to mint through `mint_by` alongside the admin. Admin only.
+    ///
+    /// The minter may be a contract: when it calls `mint_by` with its own address, the
+    /// direct invocation satisfies `require_auth` without a signature.
+    pub fn set_minter(e: Env, minter: Address) {
+        require_admin(&e);
+        e.storage().instance().set(&DataKey::Minter, &minter);
+    }
+
+    /// Propose `minter` as the next designated minter; takes effect once it calls
+    /// `accept_minter`. Admin only.
+    pub fn propose_minter(e: Env, minter: Address) {
+        require_admin(&e);
+        e.storage().instance().set(&DataKey::PendingMinter, &minter);
+    }
 
-#[contracterror]
-#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
-#[repr(u32)]
-pub enum ContractError {
-    AlreadyInitialized = 1,
-    NotInitialized = 2,
-    Unauthorized = 3,
-    WrapAlreadyExists = 4,
-    InvalidSignature = 5,
-}
+    /// Accept a pending minter proposal. Must be signed by the proposed address.
+    pub fn accept_minter(e: Env) {
+        let pending: Address = e
+            .storage()
+            .instance()
+            .get(&DataKey::PendingMinter)
+            .unwrap_or_else(|| panic_with_error!(e, ContractError::Unauthorized));
+        pending.require_auth();
+        e.storage().instance().set(&DataKey::Minter, &pending);
+        e.storage().instance().remove(&DataKey::PendingMinter);
+    }
 
-#[contract]
-pub struct StellarWrapContract;
+    /// Limit how many periods a single user may hold (default 500). Admin only.
+    pub fn set_max_periods_per_user(e: Env, max: u32) {
+        require_admin(&e);
+        e.storage()
+            .instance()
+            .set(&DataKey::MaxPeriodsPerUser, &max);
+    }
 
-#[contractimpl]
-impl StellarWrapContract {
-    /// Initialize with admin and the public key used to verify off-chain signatures.
-    pub fn initialize(e: Env, admin: Address, admin_pubkey: BytesN<32>) {
-        if e.storage().instance().has(&DataKey::Admin) {
-            panic_with_error!(e, ContractError::AlreadyInitialized);
-        }
-        e.storage().instance().set(&DataKey::Admin, &admin);
+    /// Stop minting once `max_supply` wraps are outstanding; 0 (or less) removes the cap. Admin only.
+    pub fn set_max_supply(e: Env, max_supply: i128) {
+        require_admin(&e);
+        e.storage().instance().set(&DataKey::MaxSupply, &max_supply);
+    }
+
+    /// Restrict every read of wrap contents to the wrap's owner (and `get_wrap_as` to owner
+    /// or admin). Counts and period lists stay public. Admin only.
+    pub fn set_private_mode(e: Env, enabled: bool) {
+        require_admin(&e);
+        e.storage().instance().set(&DataKey::PrivateMode, &enabled);
+    }
+
+    /// Reject mints for periods above `max_period` with `PeriodTooHigh`; 0 removes the
+    /// ceiling. Admin only.
+    pub fn set_max_period(e: Env, max_period: u64) {
+        require_admin(&e);
+        e.storage().instance().set(&DataKey::MaxPeriod, &max_period);
+    }
+
+    /// Let holders delete their own wraps through `burn`. Transfers stay blocked. Admin only.
+    pub fn set_allow_self_burn(e: Env, allowed: bool) {
+        require_admin(&e);
         e.storage()
             .instance()
-            .set(&DataKey::AdminPubKey, &admin_pubkey);
+            .set(&DataKey::AllowSelfBurn, &allowed);
     }
 
-    /// Update the admin address. Only callable by the current admin.
-    pub fn update_admin(e: Env, new_admin: Address) {
-        let current_admin: Address = e
-            .storage()
+    /// Reject mints whose data_hash the user already holds on another period, with
+    /// `DuplicateHash`. Off by default; only hashes minted while strict are tracked.
+    /// Admin only.
+    pub fn set_strict_hashes(e: Env, strict: bool) {
+        require_admin(&e);
+        e.storage().instance().set(&DataKey::StrictHashes, &strict);
+    }
+
+    /// Pause or resume every mint entry point. Admin only.
+    pub fn set_paused(e: Env, paused: bool) {
+        require_admin(&e);
+        e.storage().instance().set(&DataKey::Paused, &paused);
+    }
+
+    /// Reject mints before ledger `start`; 0 means minting is always open. Admin only.
+    pub fn set_mint_start_ledger(e: Env, start: u32) {
+        require_admin(&e);
+        e.storage()
             .instance()
-            .get(&DataKey::Admin)
-            .unwrap_or_else(|| panic_with_error!(e, ContractError::NotInitialized));
+            .set(&DataKey::MintStartLedger, &start);
+    }
 
-        current_admin.require_auth();
-        e.storage().instance().set(&DataKey::Admin, &new_admin);
+    /// Require (or stop requiring) a second signing-key signature to mint `archetype`. Admin only.
+    pub fn set_requires_cosign(e: Env, archetype: Symbol, required: bool) {
+        require_admin(&e);
+        let key = DataKey::RequiresCosign(archetype);
+        if required {
+            e.storage().persistent().set(&key, &true);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+        } else {
+            e.storage().persistent().remove(&key);
+        }
     }
 
-    /// Users claim their wrap using an Admin signature.
-    pub fn mint_wrap(
+    // --- Read Functions ---
+
+    /// Returns the user's wrap for `period`. In private mode the owner must authorize the read.
+    pub fn get_wrap(e: Env, user: Address, period: u64) -> Option<WrapRecord> {
+        require_wrap_reader(&e, &user);
+        // Changed .instance() to .persistent() to match mint_wrap
+        load_wrap(&e, &user, period)
+    }
+
+    /// `get_wrap` on behalf of `viewer`, who must authorize the call. In private mode only
+    /// the owner or the admin may read; anyone else gets `Unauthorized`.
+    pub fn get_wrap_as(
         e: Env,
+        viewer: Address,
         user: Address,
         period: u64,
-        archetype: Symbol,
-        data_hash: BytesN<32>,
-        signature: BytesN<64>,
-    ) {
-        // 1. Security: Ensure the user actually signed this transaction
+    ) -> Result<Option<WrapRecord>, ContractError> {
+        viewer.require_auth();
+        authorize_wrap_read(&e, &user, Some(&viewer))?;
+        try_load_wrap(&e, &user, period)
+    }
+
+    /// Mark the user's wrap for `period` as seen. Must be signed by the user; fails with
+    /// `WrapNotFound` if the wrap does not exist.
+    pub fn acknowledge(e: Env, user: Address, period: u64) {
         user.require_auth();
+        if !e
+            .storage()
+            .persistent()
+            .has(&DataKey::Wrap(user.clone(), period))
+        {
+            panic_with_error!(e, ContractError::WrapNotFound);
+        }
+        let key = DataKey::Acked(user, period);
+        e.storage().persistent().set(&key, &true);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_ONE_YEAR, TTL_ONE_YEAR);
+    }
 
-        // 2. Verify initialization
-        let admin_pubkey: BytesN<32> = e
+    /// Returns true once the holder has acknowledged the wrap for `period`.
+    pub fn is_acknowledged(e: Env, user: Address, period: u64) -> bool {
+        e.storage().persistent().has(&DataKey::Acked(user, period))
+    }
+
+    /// Extend a wrap's storage TTL to `ledgers` ledgers. Anyone may pay the rent; fails with
+    /// `WrapNotFound` if the wrap does not exist.
+    pub fn bump_wrap_ttl(e: Env, user: Address, period: u64, ledgers: u32) {
+        let wrap_key = DataKey::Wrap(user.clone(), period);
+        if !e.storage().persistent().has(&wrap_key) {
+            panic_with_error!(e, ContractError::WrapNotFound);
+        }
+        e.storage()
+            .persistent()
+            .extend_ttl(&wrap_key, ledgers, ledgers);
+        e.events().publish(
+            (event_prefix(&e), symbol_short!("ttl_bump"), user, period),
+            (EVENT_SCHEMA, ledgers),
+        );
+    }
+
+    /// Returns the custom attribute stored under `key` for the wrap, if any.
+    pub fn get_attribute(e: Env, user: Address, period: u64, key: Symbol) -> Option<Symbol> {
+        require_wrap_reader(&e, &user);
+        e.storage()
+            .persistent()
+            .get(&DataKey::Attr(user, period, key))
+    }
+
+    /// Like `get_wrap`, but returns `CorruptRecord` for a stored value that decodes neither
+    /// as a `WrapRecord` nor as the original four-field record, instead of aborting.
+    ///
+    /// Named `get_wrap_checked` because the generated client already has `try_get_wrap`.
+    pub fn get_wrap_checked(
+        e: Env,
+        user: Address,
+        period: u64,
+    ) -> Result<Option<WrapRecord>, ContractError> {
+        require_wrap_reader(&e, &user);
+        try_load_wrap(&e, &user, period)
+    }
+
+    /// Returns the period the user's wrap for `period` is linked to, if any.
+    pub fn get_linked(e: Env, user: Address, period: u64) -> Option<u64> {
+        require_wrap_reader(&e, &user);
+        load_wrap(&e, &user, period).and_then(|record| record.linked_period)
+    }
+
+    /// Like `get_wrap`, but fails with `WrapNotFound` when the user holds no wrap for `period`.
+    pub fn get_wrap_or_err(
+        e: Env,
+        user: Address,
+        period: u64,
+    ) -> Result<WrapRecord, ContractError> {
+        Self::get_wrap(e, user, period).ok_or(ContractError::WrapNotFound)
+    }
+
+    /// Returns the base URI followed by the lowercase hex of the wrap's `data_hash`.
+    pub fn token_uri(e: Env, user: Address, period: u64) -> Option<String> {
+        require_wrap_reader(&e, &user);
+        let record = load_wrap(&e, &user, period)?;
+        let base: String = e
             .storage()
             .instance()
-            .get(&DataKey::AdminPubKey)
-            .unwrap_or_else(|| panic_with_error!(e, ContractError::NotInitialized));
+            .get(&DataKey::BaseUri)
+            .unwrap_or_else(|| String::from_str(&e, ""));
 
-        // 3. Reconstruct Payload
-        let mut payload = Bytes::new(&e);
-        payload.append(&e.current_contract_address().to_xdr(&e));
-        payload.append(&user.clone().to_xdr(&e));
-        payload.append(&period.to_xdr(&e));
-        payload.append(&archetype.clone().to_xdr(&e));
-        payload.append(&data_hash.clone().to_xdr(&e));
+        let base_len = base.len() as usize;
+        let mut buf = [0u8; MAX_BASE_URI_LEN as usize + 64];
+        base.copy_into_slice(&mut buf[..base_len]);
+        for (i, byte) in record.data_hash.to_array().iter().enumerate() {
+            buf[base_len + i * 2] = HEX_DIGITS[(byte >> 4) as usize];
+            buf[base_len + i * 2 + 1] = HEX_DIGITS[(byte & 0x0f) as usize];
+        }
+        Some(String::from_bytes(&e, &buf[..base_len + 64]))
+    }
+
+    /// Returns every period the user holds a wrap for, in ascending order.
+    pub fn list_periods(e: Env, user: Address) -> Vec<u64> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::UserPeriods(user))
+            .unwrap_or_else(|| Vec::new(&e))
+    }
 
-        // 4. Verify Admin Signature
-        e.crypto()
-            .ed25519_verify(&admin_pubkey, &payload, &signature);
+    /// Returns the user's wrap with the lowest period, if any. The all-time wrap is left
+    /// out, since its timestamp is its latest overwrite rather than when the user joined.
+    pub fn first_wrap(e: Env, user: Address) -> Option<WrapRecord> {
+        require_wrap_reader(&e, &user);
+        let period = Self::list_periods(e.clone(), user.clone())
+            .iter()
+            .find(|period| *period != ALL_TIME_PERIOD)?;
+        load_wrap(&e, &user, period)
+    }
 
-        // 5. Check Duplicates & Store Record (Switch to Persistent)
-        let wrap_key = DataKey::Wrap(user.clone(), period);
-        if e.storage().persistent().has(&wrap_key) {
-            panic_with_error!(e, ContractError::WrapAlreadyExists);
+    /// Returns the user's wrap with the highest period, if any.
+    pub fn latest_wrap(e: Env, user: Address) -> Option<WrapRecord> {
+        require_wrap_reader(&e, &user);
+        let period = Self::list_periods(e.clone(), user.clone()).last()?;
+        load_wrap(&e, &user, period)
+    }
+
+    /// Returns the periods, ascending, whose wrap was minted at or after `since_ts`.
+    pub fn list_periods_since(e: Env, user: Address, since_ts: u64) -> Vec<u64> {
+        require_wrap_reader(&e, &user);
+        let mut periods = Vec::new(&e);
+        for record in user_wraps(&e, &user).iter() {
+            if record.timestamp >= since_ts {
+                periods.push_back(record.period);
+            }
         }
+        periods
+    }
 
-        let record = WrapRecord {
-            timestamp: e.ledger().timestamp(),
-            data_hash,
-            archetype: archetype.clone(),
-            period,
+    /// Returns `(held, span)`: how many periods the user holds and the numeric distance
+    /// between their first and latest period. The all-time wrap is left out of both.
+    pub fn span_periods(e: Env, user: Address) -> (u32, u32) {
+        let mut periods = Self::list_periods(e, user);
+        if periods.first() == Some(ALL_TIME_PERIOD) {
+            periods.pop_front();
+        }
+        let span = match (periods.first(), periods.last()) {
+            (Some(first), Some(last)) => u32::try_from(last - first).unwrap_or(u32::MAX),
+            _ => 0,
+        };
+        (periods.len(), span)
+    }
+
+    /// Returns the periods, ascending, where the user's wrap has `archetype`.
+    pub fn periods_with_archetype(e: Env, user: Address, archetype: Symbol) -> Vec<u64> {
+        require_wrap_reader(&e, &user);
+        let mut periods = Vec::new(&e);
+        for record in user_wraps(&e, &user).iter() {
+            if record.archetype == archetype {
+                periods.push_back(record.period);
+            }
+        }
+        periods
+    }
+
+    /// Sums the archetype weights of every wrap the user holds; unweighted archetypes count 1.
+    pub fn weighted_score(e: Env, user: Address) -> u64 {
+        require_wrap_reader(&e, &user);
+        score_of(&e, &user_wraps(&e, &user))
+    }
+
+    /// Bundles the user's wrap count, latest period, distinct archetypes and score in one read.
+    pub fn get_profile(e: Env, user: Address) -> Profile {
+        require_wrap_reader(&e, &user);
+        let wraps = user_wraps(&e, &user);
+        let mut archetypes: Vec<Symbol> = Vec::new(&e);
+        for record in wraps.iter() {
+            if !archetypes.contains(&record.archetype) {
+                archetypes.push_back(record.archetype);
+            }
+        }
+        Profile {
+            // Balances come from u32 counts, so this always fits
+            count: Self::balance_of(e.clone(), user) as u32,
+            latest_period: wraps.last().map(|record| record.period),
+            distinct_archetypes: archetypes.len(),
+            total_score: score_of(&e, &wraps),
+        }
+    }
+
+    /// `get_profile` for each of `users`, in the same order. At most `MAX_BATCH` users per call.
+    pub fn get_profiles(e: Env, users: Vec<Address>) -> Vec<Profile> {
+        if users.len() > MAX_BATCH {
+            panic_with_error!(e, ContractError::BatchTooLarge);
+        }
+        let mut profiles = Vec::new(&e);
+        for user in users.iter() {
+            profiles.push_back(Self::get_profile(e.clone(), user));
+        }
+        profiles
+    }
+
+    /// Returns the user's 1-based rank by `balance_of` within `cohort`, or `None` if the user
+    /// is not in it. Tied users share a rank. At most `MAX_BATCH` addresses per call.
+    pub fn rank_in(e: Env, user: Address, cohort: Vec<Address>) -> Option<u32> {
+        if cohort.len() > MAX_BATCH {
+            panic_with_error!(e, ContractError::BatchTooLarge);
+        }
+        if !cohort.contains(&user) {
+            return None;
+        }
+        let count = Self::balance_of(e.clone(), user);
+        let mut ahead: u32 = 0;
+        for member in cohort.iter() {
+            if Self::balance_of(e.clone(), member) > count {
+                ahead += 1;
+            }
+        }
+        Some(ahead + 1)
+    }
+
+    /// Returns up to `limit` periods starting at index `start`, plus the index of the next page.
+    pub fn list_periods_page(e: Env, user: Address, start: u32, limit: u32) -> Page {
+        let periods = Self::list_periods(e, user);
+        let total = periods.len();
+        let start = start.min(total);
+        let end = start.saturating_add(limit).min(total);
+
+        Page {
+            items: periods.slice(start..end),
+            next: if end < total { Some(end) } else { None },
+            total,
+        }
+    }
+
+    /// Returns `(period, archetype)` for up to `limit` of the user's periods starting at index
+    /// `start`, in period order. `limit` is capped at `MAX_BATCH` records per call.
+    pub fn list_period_archetypes(
+        e: Env,
+        user: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<(u64, Symbol)> {
+        require_wrap_reader(&e, &user);
+        let page = Self::list_periods_page(e.clone(), user.clone(), start, limit.min(MAX_BATCH));
+        let mut pairs = Vec::new(&e);
+        for period in page.items.iter() {
+            if let Some(record) = load_wrap(&e, &user, period) {
+                pairs.push_back((period, record.archetype));
+            }
+        }
+        pairs
+    }
+
+    /// Checks `signature` against the canonical mint payload for these fields using the
+    /// signing key in slot `key_index`, without minting. Returns false if that slot holds no
+    /// live key, the signature was already spent, or it does not verify.
+    pub fn verify_mint_signature(
+        e: Env,
+        user: Address,
+        period: u64,
+        archetype: Symbol,
+        data_hash: BytesN<32>,
+        signature: BytesN<64>,
+        key_index: u32,
+    ) -> bool {
+        let Some(pubkey) = signing_key(&e, key_index) else {
+            return false;
         };
+        let digest = e.crypto().sha256(&signature.clone().into()).to_bytes();
+        if e.storage().persistent().has(&DataKey::UsedSig(digest)) {
+            return false;
+        }
+        let payload = mint_payload(&e, &user, period, &archetype, &data_hash);
+        signature_valid(&pubkey, &payload, &signature)
+    }
+
+    /// Returns whether any live signing key signed the canonical mint payload for these
+    /// fields. Unlike `verify_mint_signature` it needs no key slot and ignores whether the
+    /// signature was already spent: it only answers whether the admin authorized this mint.
+    pub fn is_valid_mint_auth(
+        e: Env,
+        user: Address,
+        period: u64,
+        archetype: Symbol,
+        data_hash: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> bool {
+        let payload = mint_payload(&e, &user, period, &archetype, &data_hash);
+        signing_keys(&e)
+            .iter()
+            .any(|pubkey| signature_valid(&pubkey, &payload, &signature))
+    }
+
+    /// Returns true when `sha256(data)` matches the stored wrap's `data_hash`.
+    /// Wraps tagged with another hash algorithm can't be checked on-chain and return false.
+    pub fn verify_data(e: Env, user: Address, period: u64, data: Bytes) -> bool {
+        require_wrap_reader(&e, &user);
+        match load_wrap(&e, &user, period) {
+            Some(record) if record.hash_algo == DEFAULT_HASH_ALGO => {
+                e.crypto().sha256(&data).to_bytes() == record.data_hash
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the hash algorithm tag recorded with the wrap.
+    pub fn get_hash_algo(e: Env, user: Address, period: u64) -> Option<Symbol> {
+        require_wrap_reader(&e, &user);
+        load_wrap(&e, &user, period).map(|record| record.hash_algo)
+    }
+
+    /// Returns the periods both users hold, in ascending order. Only the first
+    /// `MAX_INDEX_SCAN` periods of each user's index are compared.
+    pub fn common_periods(e: Env, a: Address, b: Address) -> Vec<u64> {
+        let left = Self::list_periods(e.clone(), a);
+        let right = Self::list_periods(e.clone(), b);
+        let left_len = left.len().min(MAX_INDEX_SCAN);
+        let right_len = right.len().min(MAX_INDEX_SCAN);
+
+        // Both indices are sorted, so a single merge pass finds the intersection
+        let mut common = Vec::new(&e);
+        let (mut i, mut j) = (0, 0);
+        while i < left_len && j < right_len {
+            let (x, y) = (left.get_unchecked(i), right.get_unchecked(j));
+            if x == y {
+                common.push_back(x);
+                i += 1;
+                j += 1;
+            } else if x < y {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        common
+    }
 
-        // Store in persistent and extend TTL to ~1 year
-        let ttl_one_year = 17280 * 365;
-        e.storage().persistent().set(&wrap_key, &record);
+    /// Renders a `YYYYMM` period as `YYYY-MM`, or `all-time` for `ALL_TIME_PERIOD`.
+    /// Fails with `InvalidPeriod` unless the month is 1-12 and the year has at most 4 digits.
+    pub fn format_period(e: Env, period: u64) -> String {
+        if period == ALL_TIME_PERIOD {
+            return String::from_str(&e, "all-time");
+        }
+        let (year, month) = (period / 100, period % 100);
+        if year > 9999 || !(1..=12).contains(&month) {
+            panic_with_error!(e, ContractError::InvalidPeriod);
+        }
+        let digit = |value: u64| b'0' + value as u8;
+        let text = [
+            digit(year / 1000),
+            digit(year / 100 % 10),
+            digit(year / 10 % 10),
+            digit(year % 10),
+            b'-',
+            digit(month / 10),
+            digit(month % 10),
+        ];
+        String::from_bytes(&e, &text)
+    }
+
+    /// Returns the display label set for `archetype`, if any.
+    pub fn archetype_label(e: Env, archetype: Symbol) -> Option<String> {
         e.storage()
             .persistent()
-            .extend_ttl(&wrap_key, ttl_one_year, ttl_one_year);
+            .get(&DataKey::ArchetypeLabel(archetype))
+    }
 
-        // 6. Update Balance (Switch to Persistent)
-        let count_key = DataKey::WrapCount(user.clone());
-        let current_count: u32 = e.storage().persistent().get(&count_key).unwrap_or(0);
+    /// Returns the last `RECENT_MINTS` minted (user, period) pairs, oldest first.
+    pub fn recent_mints(e: Env) -> Vec<(Address, u64)> {
         e.storage()
             .persistent()
-            .set(&count_key, &(current_count + 1));
+            .get(&DataKey::RecentMints)
+            .unwrap_or_else(|| Vec::new(&e))
+    }
+
+    /// Returns how many wraps are currently held with `archetype`.
+    pub fn archetype_count(e: Env, archetype: Symbol) -> u32 {
         e.storage()
             .persistent()
-            .extend_ttl(&count_key, ttl_one_year, ttl_one_year);
+            .get(&DataKey::ArchetypeCount(archetype))
+            .unwrap_or(0)
+    }
 
-        // 7. Emit Event
-        e.events()
-            .publish((symbol_short!("mint"), user, period), archetype);
+    /// Returns every archetype that has been minted at least once, in first-minted order.
+    pub fn list_archetypes(e: Env) -> Vec<Symbol> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::SeenArchetypes)
+            .unwrap_or_else(|| Vec::new(&e))
     }
 
-    // --- Read Functions ---
+    /// Returns up to `n` of the top holders by wrap count, highest first. Only the top
+    /// `LEADERBOARD_SIZE` holders are tracked; one that falls out re-enters on its next mint.
+    pub fn top_holders(e: Env, n: u32) -> Vec<(Address, u32)> {
+        let board: Vec<(Address, u32)> = e
+            .storage()
+            .persistent()
+            .get(&DataKey::CountLeaderboard)
+            .unwrap_or_else(|| Vec::new(&e));
+        board.slice(0..n.min(board.len()))
+    }
 
-    pub fn get_wrap(e: Env, user: Address, period: u64) -> Option<WrapRecord> {
-        // Changed .instance() to .persistent() to match mint_wrap
-        e.storage().persistent().get(&DataKey::Wrap(user, period))
+    /// Counts how many of `users` hold each archetype for `period`, in order of first
+    /// appearance. At most `MAX_BATCH` users per call.
+    pub fn archetype_histogram(e: Env, users: Vec<Address>, period: u64) -> Vec<(Symbol, u32)> {
+        if users.len() > MAX_BATCH {
+            panic_with_error!(e, ContractError::BatchTooLarge);
+        }
+
+        let mut histogram: Vec<(Symbol, u32)> = Vec::new(&e);
+        for user in users.iter() {
+            require_wrap_reader(&e, &user);
+            let Some(record) = load_wrap(&e, &user, period) else {
+                continue;
+            };
+            match histogram
+                .iter()
+                .position(|(archetype, _)| archetype == record.archetype)
+            {
+                Some(i) => {
+                    let (archetype, count) = histogram.get_unchecked(i as u32);
+                    histogram.set(i as u32, (archetype, count + 1));
+                }
+                None => histogram.push_back((record.archetype, 1)),
+            }
+        }
+        histogram
+    }
+
+    /// Stable identifier for a (user, period) wrap: `sha256(contract || user || period)`
+    /// over their XDR encodings. Computable off-chain and independent of whether the wrap exists.
+    pub fn wrap_id(e: Env, user: Address, period: u64) -> BytesN<32> {
+        let mut preimage = Bytes::new(&e);
+        preimage.append(&e.current_contract_address().to_xdr(&e));
+        preimage.append(&user.to_xdr(&e));
+        preimage.append(&period.to_xdr(&e));
+        e.crypto().sha256(&preimage).to_bytes()
+    }
+
+    /// Returns the XDR encoding of all the user's wraps as a `Vec<WrapRecord>`, for snapshotting.
+    pub fn export_user(e: Env, user: Address) -> Bytes {
+        require_wrap_reader(&e, &user);
+        user_wraps(&e, &user).to_xdr(&e)
     }
 
+    /// Returns true if the wrap exists and is older than the configured wrap lifetime.
+    pub fn is_expired(e: Env, user: Address, period: u64) -> bool {
+        require_wrap_reader(&e, &user);
+        load_wrap(&e, &user, period)
+            .is_some_and(|record| record_expired(&e, &record, wrap_lifetime(&e)))
+    }
+
+    /// Counts the wraps `id` holds, leaving out expired ones once a wrap lifetime is set.
     pub fn balance_of(e: Env, id: Address) -> i128 {
+        let lifetime = wrap_lifetime(&e);
+        if lifetime != 0 {
+            let live = user_wraps(&e, &id)
+                .iter()
+                .filter(|record| !record_expired(&e, record, lifetime))
+                .count();
+            return live as i128;
+        }
+
         let count_key = DataKey::WrapCount(id);
         // Changed .instance() to .persistent() to match mint_wrap
         e.storage()
@@ -129,22 +2681,209 @@ impl StellarWrapContract {
             .unwrap_or(0) as i128
     }
 
+    /// Returns the public keys currently accepted for wrap signatures.
+    pub fn list_signers(e: Env) -> Vec<BytesN<32>> {
+        signing_keys(&e)
+    }
+
+    /// Returns every signing key slot; the index is the `key_index` mint calls take. Slots
+    /// of removed keys are `None` and are never reused.
+    pub fn signing_key_slots(e: Env) -> Vec<Option<BytesN<32>>> {
+        signing_key_slots(&e)
+    }
+
+    /// Returns `balance_of(id)` together with the highest period `id` holds, if any.
+    pub fn balance_and_latest(e: Env, id: Address) -> (i128, Option<u64>) {
+        let latest = Self::list_periods(e.clone(), id.clone()).last();
+        (Self::balance_of(e, id), latest)
+    }
+
+    /// Returns `balance_of(a) - balance_of(b)`, negative when `b` holds more wraps.
+    pub fn count_diff(e: Env, a: Address, b: Address) -> i64 {
+        // Balances are bounded by u32 wrap counts, so the difference always fits
+        (Self::balance_of(e.clone(), a) - Self::balance_of(e, b)) as i64
+    }
+
+    /// Returns the stored `WrapCount` as-is, for diagnosing drift from the period index.
+    pub fn raw_count(e: Env, user: Address) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::WrapCount(user))
+            .unwrap_or(0)
+    }
+
+    /// Returns true when the stored `WrapCount` equals the length of the user's period
+    /// index. A false result is drift that `recount_user` can repair.
+    pub fn count_matches_index(e: Env, user: Address) -> bool {
+        Self::raw_count(e.clone(), user.clone()) == Self::list_periods(e, user).len()
+    }
+
+    /// Returns true if `who` is the current admin; false before initialization.
+    pub fn is_admin(e: Env, who: Address) -> bool {
+        e.storage()
+            .instance()
+            .get::<_, Address>(&DataKey::Admin)
+            .is_some_and(|admin| admin == who)
+    }
+
+    /// Returns who authorized the user's wrap for `period`: the admin for signature-based
+    /// mints, otherwise the minter that called `mint_by`.
+    pub fn get_minter_of(e: Env, user: Address, period: u64) -> Option<Address> {
+        require_wrap_reader(&e, &user);
+        load_wrap(&e, &user, period).map(|record| record.minted_by)
+    }
+
+    /// Returns how many wraps `minter` has minted. Signature-based mints count toward the admin.
+    pub fn minted_by(e: Env, minter: Address) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::MintedBy(minter))
+            .unwrap_or(0)
+    }
+
+    /// Returns how many wraps have been minted for `period` across all users. Revokes do not lower it.
+    pub fn period_total(e: Env, period: u64) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::PeriodMinted(period))
+            .unwrap_or(0)
+    }
+
+    /// Returns true while the admin has minting paused.
+    pub fn is_paused(e: Env) -> bool {
+        e.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
+    /// Returns the current minting settings, with defaults for anything never set.
+    pub fn get_config(e: Env) -> ContractConfig {
+        let instance = e.storage().instance();
+        ContractConfig {
+            paused: Self::is_paused(e.clone()),
+            mint_start: instance.get(&DataKey::MintStartLedger).unwrap_or(0),
+            ledger_mint_cap: instance.get(&DataKey::LedgerMintCap).unwrap_or(0),
+            max_supply: instance.get(&DataKey::MaxSupply).unwrap_or(0),
+            max_periods_per_user: max_periods_per_user(&e),
+            allow_self_mint: instance.get(&DataKey::AllowSelfMint).unwrap_or(false),
+            wrap_lifetime: wrap_lifetime(&e),
+            minter: instance.get(&DataKey::Minter),
+            max_period: instance.get(&DataKey::MaxPeriod).unwrap_or(0),
+        }
+    }
+
+    /// SEP-41 total supply: wraps currently held across all users, net of revocations.
+    pub fn total_supply(e: Env) -> i128 {
+        e.storage()
+            .instance()
+            .get::<_, u32>(&DataKey::TotalSupply)
+            .unwrap_or(0) as i128
+    }
+
+    /// Returns contract-wide counters for budgeting storage and TTL extensions.
+    pub fn storage_stats(e: Env) -> StorageStats {
+        let instance = e.storage().instance();
+        StorageStats {
+            total_supply: Self::total_supply(e.clone()),
+            holder_count: instance.get(&DataKey::HolderCount).unwrap_or(0),
+            distinct_periods: instance.get(&DataKey::DistinctPeriods).unwrap_or(0),
+        }
+    }
+
+    /// Returns total supply, holder count, code version and pause state in one call.
+    pub fn stats(e: Env) -> GlobalStats {
+        let instance = e.storage().instance();
+        GlobalStats {
+            total_supply: Self::total_supply(e.clone()),
+            unique_holders: instance.get(&DataKey::HolderCount).unwrap_or(0),
+            version: instance.get(&DataKey::CodeVersion).unwrap_or(0),
+            paused: Self::is_paused(e.clone()),
+        }
+    }
+
     pub fn get_admin(e: Env) -> Option<Address> {
         // This stays .instance() because initialize() uses instance()
         e.storage().instance().get(&DataKey::Admin)
     }
 
+    /// Returns true once `initialize` has stored an admin.
+    pub fn is_initialized(e: Env) -> bool {
+        e.storage().instance().has(&DataKey::Admin)
+    }
+
+    /// Wraps are soulbound: always fails with `SbtTransferNotAllowed`.
+    pub fn transfer(
+        e: Env,
+        from: Address,
+        _to: Address,
+        _amount: i128,
+    ) -> Result<(), ContractError> {
+        reject_sbt_operation(&e, from, symbol_short!("transfer"))
+    }
+
+    /// Wraps are soulbound: always fails with `SbtTransferNotAllowed`.
+    pub fn approve(
+        e: Env,
+        from: Address,
+        _spender: Address,
+        _amount: i128,
+        _expiration_ledger: u32,
+    ) -> Result<(), ContractError> {
+        reject_sbt_operation(&e, from, symbol_short!("approve"))
+    }
+
+    /// Fails with `SbtTransferNotAllowed` unless the admin enabled self-burn, in which case
+    /// `from` may delete their own highest-period wrap. `amount` is ignored: one wrap is
+    /// burned per call, and `WrapNotFound` is returned if `from` holds none.
+    pub fn burn(e: Env, from: Address, _amount: i128) -> Result<(), ContractError> {
+        let allow_self_burn: bool = e
+            .storage()
+            .instance()
+            .get(&DataKey::AllowSelfBurn)
+            .unwrap_or(false);
+        if !allow_self_burn {
+            return reject_sbt_operation(&e, from, symbol_short!("burn"));
+        }
+        from.require_auth();
+        let period = Self::list_periods(e.clone(), from.clone())
+            .last()
+            .ok_or(ContractError::WrapNotFound)?;
+        revoke(&e, &from, period);
+        Ok(())
+    }
+
     pub fn name(e: Env) -> String {
         String::from_str(&e, "Stellar Wrap Registry")
     }
 
     pub fn symbol(e: Env) -> String {
-        String::from_str(&e, "WRAP")
+        e.storage()
+            .instance()
+            .get(&DataKey::TokenSymbol)
+            .unwrap_or_else(|| String::from_str(&e, "WRAP"))
     }
 
     pub fn decimals(_e: Env) -> u32 {
         0
     }
+
+    /// Returns name, symbol and decimals together.
+    pub fn metadata(e: Env) -> TokenMetadata {
+        TokenMetadata {
+            name: Self::name(e.clone()),
+            symbol: Self::symbol(e.clone()),
+            decimals: Self::decimals(e),
+        }
+    }
+
+    /// Longer registry description for wallets; empty until the admin sets one.
+    pub fn description(e: Env) -> String {
+        e.storage()
+            .instance()
+            .get(&DataKey::Description)
+            .unwrap_or_else(|| String::from_str(&e, ""))
+    }
 }
 
 #[cfg(test)]
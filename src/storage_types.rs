@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, BytesN, Symbol};
+use soroban_sdk::{contracttype, Address, BytesN, String, Symbol, Vec};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -6,7 +6,138 @@ pub struct WrapRecord {
     pub timestamp: u64,
     pub data_hash: BytesN<32>,
     pub archetype: Symbol,
-    pub period: u64, // Standardized to u64 for better indexing/sorting
+    pub period: u64,                // Standardized to u64 for better indexing/sorting
+    pub hash_algo: Symbol,          // How data_hash was computed, e.g. sha256
+    pub ledger_seq: u32,            // Ledger sequence at mint time, 0 for migrated legacy records
+    pub consented: bool,            // Recipient signed an acknowledgment via mint_wrap_with_consent
+    pub global_id: u64,             // Contract-wide mint sequence number, starting at 0
+    pub linked_period: Option<u64>, // Earlier period this wrap corrects or continues
+    pub minted_by: Address,         // Admin or minter that authorized the mint
+    pub signer_index: Option<u32>,  // Signing key slot that verified the mint (None if unsigned)
+}
+
+/// A single signed mint inside a `mint_wrap_batch` call
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintEntry {
+    pub user: Address,
+    pub period: u64,
+    pub archetype: Symbol,
+    pub data_hash: BytesN<32>,
+    pub signature: BytesN<64>,
+    pub key_index: u32, // Signing key slot that produced `signature`
+}
+
+/// SEP-41 token metadata returned in a single call
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u32,
+}
+
+/// One page of a user's period index
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Page {
+    pub items: Vec<u64>,
+    /// Start index of the following page, `None` once the last page is returned
+    pub next: Option<u32>,
+    pub total: u32,
+}
+
+/// Contract-wide counters for monitoring the storage footprint
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StorageStats {
+    /// Wraps currently held across all users
+    pub total_supply: i128,
+    /// Users holding at least one wrap
+    pub holder_count: u32,
+    /// Periods that have ever been minted
+    pub distinct_periods: u32,
+}
+
+/// Registry-wide figures for a status page
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GlobalStats {
+    /// Wraps currently held across all users
+    pub total_supply: i128,
+    /// Users holding at least one wrap
+    pub unique_holders: u32,
+    /// Code version, bumped by each `upgrade` (0 = as deployed)
+    pub version: u32,
+    /// Whether minting is currently paused
+    pub paused: bool,
+}
+
+/// A user's headline numbers for a profile page
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Profile {
+    /// Same as `balance_of`, so expired wraps are left out once a lifetime is set
+    pub count: u32,
+    pub latest_period: Option<u64>,
+    /// Number of different archetypes across the user's wraps
+    pub distinct_archetypes: u32,
+    /// Sum of archetype weights over the held wraps, as in `weighted_score`
+    pub total_score: u64,
+}
+
+/// Admin-tunable minting settings, read back in one call
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractConfig {
+    /// Whether minting is currently paused
+    pub paused: bool,
+    /// Ledger sequence before which mints are rejected (0 = open)
+    pub mint_start: u32,
+    /// Maximum mints per ledger across the contract (0 = unlimited)
+    pub ledger_mint_cap: u32,
+    /// Cap on total supply (0 = uncapped)
+    pub max_supply: i128,
+    /// Maximum number of periods one user may hold
+    pub max_periods_per_user: u32,
+    /// Whether the admin may mint to its own address
+    pub allow_self_mint: bool,
+    /// Seconds a wrap counts toward balance_of (0 = never expires)
+    pub wrap_lifetime: u64,
+    /// Address allowed to mint through `mint_by` besides the admin
+    pub minter: Option<Address>,
+    /// Highest period that may be minted (0 = no ceiling)
+    pub max_period: u64,
+}
+
+/// WrapRecord shape written by schema v1 deployments, which keyed periods by Symbol
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyWrapRecord {
+    pub timestamp: u64,
+    pub data_hash: BytesN<32>,
+    pub archetype: Symbol,
+    pub period: Symbol,
+}
+
+/// WrapRecord shape written before the record grew past its first four fields. Reads fill
+/// the rest in: sha256, ledger 0, not consented, `global_id` `u64::MAX` (never numbered),
+/// no link, minted by the admin and no recorded signing key slot.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyU64WrapRecord {
+    pub timestamp: u64,
+    pub data_hash: BytesN<32>,
+    pub archetype: Symbol,
+    pub period: u64,
+}
+
+/// Storage keys from schema v1, kept only so `migrate_v1_to_v2` can read them
+#[contracttype]
+#[derive(Clone)]
+pub enum LegacyDataKey {
+    /// Stores a LegacyWrapRecord (mapped by User and period Symbol such as `2024_01`)
+    Wrap(Address, Symbol),
 }
 
 #[contracttype]
@@ -14,11 +145,104 @@ pub struct WrapRecord {
 pub enum DataKey {
     /// Stores the Address of the admin
     Admin,
-    /// Stores the BytesN<32> public key for Ed25519 verification
+    /// Stores the Vec<Option<BytesN<32>>> signing key slots; removed keys leave a None
     AdminPubKey,
     /// Stores individual WrapRecords (mapped by User and Period)
     /// Using u64 for period ensures consistent indexing
     Wrap(Address, u64),
     /// Stores the total number of wraps for a specific user (for balance_of)
     WrapCount(Address),
+    /// Stores the String prefix used by `token_uri`
+    BaseUri,
+    /// Stores the Address of the token `claim_wrap` fees are paid in
+    FeeToken,
+    /// Stores the i128 fee charged per `claim_wrap` (0 = free)
+    FeeAmount,
+    /// Stores the u64 number of seconds a wrap counts toward balance_of (0 = never expires)
+    WrapLifetime,
+    /// Marks an address pre-registered as an eligible mint recipient
+    Eligible(Address),
+    /// Stores whether mints are limited to Eligible recipients (defaults to false)
+    EligibilityEnforced,
+    /// Stores a custom Symbol attribute on a wrap (mapped by User, Period and attribute key)
+    Attr(Address, u64, Symbol),
+    /// Marks a wrap its holder has acknowledged as seen (mapped by User and Period)
+    Acked(Address, u64),
+    /// Stores the Vec<u32> of holder counts that trigger a milestone event
+    Milestones,
+    /// Stores the String description wallets can show for the registry
+    Description,
+    /// Stores the String token symbol reported by symbol() (defaults to "WRAP")
+    TokenSymbol,
+    /// Stores whether the admin may mint wraps to its own address (defaults to false)
+    AllowSelfMint,
+    /// Stores whether holders may burn their own wraps (defaults to false)
+    AllowSelfBurn,
+    /// Stores the Symbol emitted as the first topic of every event except the SEP-41 `mint`
+    /// balance event, whose shape the standard fixes
+    EventPrefix,
+    /// Stores the sorted Vec<u64> of periods a user holds
+    UserPeriods(Address),
+    /// Stores the u32 maximum number of mints per ledger (0 = unlimited)
+    LedgerMintCap,
+    /// Stores the (ledger sequence, mints so far) tuple used to enforce LedgerMintCap
+    LedgerMintCount,
+    /// Stores true once the admin has irreversibly renounced control
+    Renounced,
+    /// Stores the u32 score weight of an archetype (defaults to 1)
+    ArchetypeWeight(Symbol),
+    /// Stores the human-readable String label shown for an archetype
+    ArchetypeLabel(Symbol),
+    /// Stores true once the admin has permanently given up configuration changes
+    ControlsRenounced,
+    /// Marks an archetype whose mints need signatures from two signing keys
+    RequiresCosign(Symbol),
+    /// Stores the u32 maximum number of periods one user may hold (defaults to 500)
+    MaxPeriodsPerUser,
+    /// Stores the u64 highest period that may be minted (0 = no ceiling)
+    MaxPeriod,
+    /// Stores the i128 cap on total supply (0 = uncapped)
+    MaxSupply,
+    /// Stores whether wrap record reads are restricted to the owner or the admin (defaults to false)
+    PrivateMode,
+    /// Stores whether a user's data_hash must be unique across their wraps (defaults to false)
+    StrictHashes,
+    /// Marks that the user holds a wrap with this data_hash
+    UserHash(Address, BytesN<32>),
+    /// Stores whether minting is paused (defaults to false)
+    Paused,
+    /// Stores the u32 ledger sequence before which mints are rejected (0 = open)
+    MintStartLedger,
+    /// Stores the Address allowed to mint via `mint_by` besides the admin
+    Minter,
+    /// Stores the Address proposed as minter until it calls `accept_minter`
+    PendingMinter,
+    /// Stores the u32 number of wraps minted by an admin or minter
+    MintedBy(Address),
+    /// Stores the u64 global_id the next new wrap will receive
+    NextWrapId,
+    /// Stores the u32 number of wraps currently held across all users
+    TotalSupply,
+    /// Stores the u32 number of users holding at least one wrap
+    HolderCount,
+    /// Stores the u32 number of periods ever minted
+    DistinctPeriods,
+    /// Marks a period that has been minted at least once
+    PeriodSeen(u64),
+    /// Stores the Vec<Symbol> of distinct archetypes ever minted, in first-minted order
+    SeenArchetypes,
+    /// Stores the Vec<(Address, u32)> of top holders by wrap count, highest first
+    CountLeaderboard,
+    /// Stores the u32 number of wraps currently held with an archetype
+    ArchetypeCount(Symbol),
+    /// Stores the Vec<(Address, u64)> of the most recent mints, oldest first
+    RecentMints,
+    /// Stores the u32 number of wraps ever minted for a period across all users
+    PeriodMinted(u64),
+    /// Marks a mint signature (keyed by its sha256 digest) that has already been accepted
+    UsedSig(BytesN<32>),
+    /// Stores the u32 storage schema version (absent = 1)
+    SchemaVersion,
+    /// Stores the u32 number of times the contract code was upgraded
+    CodeVersion,
 }
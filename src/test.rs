@@ -3,9 +3,10 @@ use super::*;
 use ed25519_dalek::{Signer, SigningKey};
 use soroban_sdk::{
     symbol_short,
-    testutils::{Address as _, Events},
+    testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke},
+    vec,
     xdr::ToXdr,
-    Address, Bytes, BytesN, Env, String, Symbol, TryIntoVal,
+    Address, Bytes, BytesN, Env, IntoVal, String, Symbol, TryIntoVal,
 };
 
 fn sign_payload(
@@ -17,13 +18,29 @@ fn sign_payload(
     archetype: &Symbol,
     data_hash: &BytesN<32>,
 ) -> BytesN<64> {
+    let payload = mint_payload_bytes(env, contract, user, period, archetype, data_hash);
+    sign_bytes(env, signer, &payload)
+}
+
+/// The plain five-field mint payload; tests append signed terms to it.
+fn mint_payload_bytes(
+    env: &Env,
+    contract: &Address,
+    user: &Address,
+    period: u64,
+    archetype: &Symbol,
+    data_hash: &BytesN<32>,
+) -> Bytes {
     let mut payload = Bytes::new(env);
     payload.append(&contract.to_xdr(env));
     payload.append(&user.clone().to_xdr(env));
     payload.append(&period.to_xdr(env));
     payload.append(&archetype.clone().to_xdr(env));
     payload.append(&data_hash.clone().to_xdr(env));
+    payload
+}
 
+fn sign_bytes(env: &Env, signer: &SigningKey, payload: &Bytes) -> BytesN<64> {
     let mut out = [0u8; 512];
     let len = payload.len() as usize;
     payload.copy_into_slice(&mut out[..len]);
@@ -32,6 +49,43 @@ fn sign_payload(
     BytesN::from_array(env, &signature.to_bytes())
 }
 
+/// Registers and initializes a contract, returning its client, signing key and admin.
+fn setup(env: &Env) -> (StellarWrapContractClient<'_>, SigningKey, Address) {
+    let contract_id = env.register_contract(None, StellarWrapContract);
+    let client = StellarWrapContractClient::new(env, &contract_id);
+
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    let admin_pubkey = BytesN::from_array(env, &signing_key.verifying_key().to_bytes());
+    let admin = Address::generate(env);
+
+    client.initialize(&admin, &admin_pubkey);
+    env.mock_all_auths();
+
+    (client, signing_key, admin)
+}
+
+/// Signs and mints a wrap for `user` through the given client.
+fn mint(
+    env: &Env,
+    client: &StellarWrapContractClient,
+    signer: &SigningKey,
+    user: &Address,
+    period: u64,
+    archetype: &Symbol,
+    data_hash: &BytesN<32>,
+) {
+    let signature = sign_payload(
+        env,
+        signer,
+        &client.address,
+        user,
+        period,
+        archetype,
+        data_hash,
+    );
+    client.mint_wrap(user, &period, archetype, data_hash, &signature, &0, &None);
+}
+
 #[test]
 fn test_minting_flow() {
     let env = Env::default();
@@ -59,7 +113,15 @@ fn test_minting_flow() {
         &archetype,
         &dummy_hash,
     );
-    client.mint_wrap(&user, &period, &archetype, &dummy_hash, &signature);
+    client.mint_wrap(
+        &user,
+        &period,
+        &archetype,
+        &dummy_hash,
+        &signature,
+        &0,
+        &None,
+    );
 
     let wrap = client.get_wrap(&user, &period).unwrap();
     assert_eq!(wrap.data_hash, dummy_hash);
@@ -92,18 +154,21 @@ fn test_mint_emits_event() {
         &hash,
     );
 
-    client.mint_wrap(&user, &period, &archetype, &hash, &signature);
+    client.mint_wrap(&user, &period, &archetype, &hash, &signature, &0, &None);
 
     let events = env.events().all();
     let last_event = events.last().expect("No events found");
     let (_, topics, data) = last_event;
 
     // Convert Vals to concrete types for comparison
-    let event_topic: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
-    let event_user: Address = topics.get(1).unwrap().try_into_val(&env).unwrap();
-    let event_period: u64 = topics.get(2).unwrap().try_into_val(&env).unwrap();
-    let event_archetype: Symbol = data.try_into_val(&env).unwrap();
+    let event_prefix: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    let event_topic: Symbol = topics.get(1).unwrap().try_into_val(&env).unwrap();
+    let event_user: Address = topics.get(2).unwrap().try_into_val(&env).unwrap();
+    let event_period: u64 = topics.get(3).unwrap().try_into_val(&env).unwrap();
+    let (event_schema, event_archetype): (u32, Symbol) = data.try_into_val(&env).unwrap();
 
+    assert_eq!(event_schema, EVENT_SCHEMA);
+    assert_eq!(event_prefix, symbol_short!("wrap"));
     assert_eq!(event_topic, symbol_short!("mint"));
     assert_eq!(event_user, user);
     assert_eq!(event_period, period);
@@ -136,7 +201,7 @@ fn test_balance_of_and_count() {
         &archetype,
         &hash,
     );
-    client.mint_wrap(&user, &2021, &archetype, &hash, &sig1);
+    client.mint_wrap(&user, &2021, &archetype, &hash, &sig1, &0, &None);
 
     let sig2 = sign_payload(
         &env,
@@ -147,7 +212,7 @@ fn test_balance_of_and_count() {
         &archetype,
         &hash,
     );
-    client.mint_wrap(&user, &2022, &archetype, &hash, &sig2);
+    client.mint_wrap(&user, &2022, &archetype, &hash, &sig2, &0, &None);
 
     assert_eq!(client.balance_of(&user), 2);
 }
@@ -194,8 +259,28 @@ fn test_duplicate_period_fails() {
         &hash,
     );
 
-    client.mint_wrap(&user, &period, &archetype, &hash, &sig);
-    client.mint_wrap(&user, &period, &archetype, &hash, &sig);
+    client.mint_wrap(&user, &period, &archetype, &hash, &sig, &0, &None);
+
+    // A freshly signed payload for the same period is still a duplicate
+    let other_archetype = symbol_short!("defi");
+    let other_sig = sign_payload(
+        &env,
+        &signing_key,
+        &contract_id,
+        &user,
+        period,
+        &other_archetype,
+        &hash,
+    );
+    client.mint_wrap(
+        &user,
+        &period,
+        &other_archetype,
+        &hash,
+        &other_sig,
+        &0,
+        &None,
+    );
 }
 
 #[test]
@@ -228,3 +313,3980 @@ fn test_token_metadata() {
     );
     assert_eq!(client.symbol(), String::from_str(&env, "WRAP"));
 }
+
+#[test]
+fn test_token_uri_appends_hex_hash() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+
+    let mut raw = [0u8; 32];
+    for (i, byte) in raw.iter_mut().enumerate() {
+        *byte = (i as u8) * 8;
+    }
+    let hash = BytesN::from_array(&env, &raw);
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202512,
+        &symbol_short!("arch"),
+        &hash,
+    );
+
+    client.set_base_uri(&String::from_str(&env, "https://wrap.example/meta/"));
+
+    let expected = String::from_str(
+        &env,
+        "https://wrap.example/meta/0008101820283038404850586068707880889098a0a8b0b8c0c8d0d8e0e8f0f8",
+    );
+    assert_eq!(client.token_uri(&user, &202512), Some(expected));
+    assert_eq!(client.token_uri(&user, &202601), None);
+}
+
+#[test]
+fn test_self_mint_guard() {
+    let env = Env::default();
+    let (client, signing_key, admin) = setup(&env);
+    let user = Address::generate(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[5u8; 32]);
+
+    // Minting to the admin is rejected by default
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &admin,
+        202512,
+        &archetype,
+        &hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap(&admin, &202512, &archetype, &hash, &signature, &0, &None),
+        Err(Ok(ContractError::SelfMintDisallowed.into()))
+    );
+
+    // Non-admins are unaffected by the flag
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202512,
+        &archetype,
+        &hash,
+    );
+    assert_eq!(client.balance_of(&user), 1);
+
+    // Once enabled, the admin can hold a wrap too
+    client.set_allow_self_mint(&true);
+    client.mint_wrap(&admin, &202512, &archetype, &hash, &signature, &0, &None);
+    assert_eq!(client.balance_of(&admin), 1);
+}
+
+#[test]
+fn test_metadata_struct() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, StellarWrapContract);
+    let client = StellarWrapContractClient::new(&env, &contract_id);
+
+    let metadata = client.metadata();
+    assert_eq!(
+        metadata.name,
+        String::from_str(&env, "Stellar Wrap Registry")
+    );
+    assert_eq!(metadata.symbol, String::from_str(&env, "WRAP"));
+    assert_eq!(metadata.decimals, 0);
+}
+
+#[test]
+fn test_custom_event_prefix() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+
+    client.set_event_prefix(&symbol_short!("tenant1"));
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202512,
+        &symbol_short!("arch"),
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+
+    let (_, topics, _) = env.events().all().last().unwrap();
+    let prefix: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    let topic: Symbol = topics.get(1).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(prefix, symbol_short!("tenant1"));
+    assert_eq!(topic, symbol_short!("mint"));
+}
+
+#[test]
+fn test_list_periods_page() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[9u8; 32]);
+
+    // Mint out of order to check the index stays sorted
+    for period in [202503u64, 202501, 202505, 202502, 202504] {
+        mint(
+            &env,
+            &client,
+            &signing_key,
+            &user,
+            period,
+            &archetype,
+            &hash,
+        );
+    }
+
+    let page = client.list_periods_page(&user, &0, &2);
+    assert_eq!(page.items, vec![&env, 202501u64, 202502]);
+    assert_eq!(page.next, Some(2));
+    assert_eq!(page.total, 5);
+
+    let page = client.list_periods_page(&user, &2, &2);
+    assert_eq!(page.items, vec![&env, 202503u64, 202504]);
+    assert_eq!(page.next, Some(4));
+    assert_eq!(page.total, 5);
+
+    let page = client.list_periods_page(&user, &4, &2);
+    assert_eq!(page.items, vec![&env, 202505u64]);
+    assert_eq!(page.next, None);
+    assert_eq!(page.total, 5);
+}
+
+#[test]
+fn test_list_signers() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, StellarWrapContract);
+    let client = StellarWrapContractClient::new(&env, &contract_id);
+
+    assert_eq!(client.list_signers().len(), 0);
+
+    let pubkey = BytesN::from_array(&env, &[1u8; 32]);
+    client.initialize(&Address::generate(&env), &pubkey);
+    assert_eq!(client.list_signers(), vec![&env, pubkey]);
+}
+
+#[test]
+fn test_signing_key_rotation_overlap() {
+    let env = Env::default();
+    let (client, old_key, _admin) = setup(&env);
+    let new_key = SigningKey::from_bytes(&[8u8; 32]);
+    let new_pubkey = BytesN::from_array(&env, &new_key.verifying_key().to_bytes());
+    let old_pubkey = BytesN::from_array(&env, &old_key.verifying_key().to_bytes());
+    let user = Address::generate(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[3u8; 32]);
+
+    client.add_signing_key(&new_pubkey);
+    assert_eq!(
+        client.list_signers(),
+        vec![&env, old_pubkey.clone(), new_pubkey.clone()]
+    );
+
+    // Payloads signed by either key are accepted during the overlap
+    mint(&env, &client, &old_key, &user, 202511, &archetype, &hash);
+    let sig = sign_payload(
+        &env,
+        &new_key,
+        &client.address,
+        &user,
+        202512,
+        &archetype,
+        &hash,
+    );
+    client.mint_wrap(&user, &202512, &archetype, &hash, &sig, &1, &None);
+    assert_eq!(client.balance_of(&user), 2);
+    assert_eq!(
+        client.get_wrap(&user, &202511).unwrap().signer_index,
+        Some(0)
+    );
+    assert_eq!(
+        client.get_wrap(&user, &202512).unwrap().signer_index,
+        Some(1)
+    );
+
+    // Batch entries name their key slot too
+    let mut entries = signed_entries(&env, &client, &new_key, &user, 1);
+    let mut entry = entries.get(0).unwrap();
+    entry.key_index = 1;
+    entries.set(0, entry);
+    client.mint_wrap_batch(&entries);
+    assert_eq!(client.balance_of(&user), 3);
+
+    // Once the old key is removed its slot is empty, and the new key keeps slot 1
+    client.remove_signing_key(&old_pubkey);
+    assert_eq!(client.list_signers(), vec![&env, new_pubkey.clone()]);
+    assert_eq!(
+        client.signing_key_slots(),
+        vec![&env, None, Some(new_pubkey)]
+    );
+
+    let old_sig = sign_payload(
+        &env,
+        &old_key,
+        &client.address,
+        &user,
+        202601,
+        &archetype,
+        &hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap(&user, &202601, &archetype, &hash, &old_sig, &0, &None),
+        Err(Ok(ContractError::InvalidSignature.into()))
+    );
+    assert!(!client.verify_mint_signature(&user, &202601, &archetype, &hash, &old_sig, &0));
+
+    let new_sig = sign_payload(
+        &env,
+        &new_key,
+        &client.address,
+        &user,
+        202601,
+        &archetype,
+        &hash,
+    );
+    assert!(client.verify_mint_signature(&user, &202601, &archetype, &hash, &new_sig, &1));
+    client.claim_wrap(&user, &202601, &archetype, &hash, &new_sig, &1);
+    assert_eq!(client.balance_of(&user), 4);
+
+    // Wraps signed by the same key keep the same slot across the removal
+    assert_eq!(
+        client.get_wrap(&user, &202601).unwrap().signer_index,
+        Some(1)
+    );
+    assert_eq!(
+        client.get_wrap(&user, &202601).unwrap().signer_index,
+        client.get_wrap(&user, &202512).unwrap().signer_index
+    );
+}
+
+#[test]
+fn test_signer_set_bounds() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let pubkey = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+
+    assert_eq!(
+        client.try_remove_signing_key(&pubkey),
+        Err(Ok(ContractError::InvalidSignerSet.into()))
+    );
+
+    client.add_signing_key(&BytesN::from_array(&env, &[2u8; 32]));
+    assert_eq!(
+        client.try_add_signing_key(&BytesN::from_array(&env, &[3u8; 32])),
+        Err(Ok(ContractError::InvalidSignerSet.into()))
+    );
+    assert_eq!(
+        client.try_remove_signing_key(&BytesN::from_array(&env, &[4u8; 32])),
+        Err(Ok(ContractError::SignerNotFound.into()))
+    );
+}
+
+#[test]
+fn test_claim_wrap_with_admin_signature() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+    let archetype = symbol_short!("defi");
+    let hash = BytesN::from_array(&env, &[6u8; 32]);
+
+    let admin_signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202512,
+        &archetype,
+        &hash,
+    );
+    client.claim_wrap(&user, &202512, &archetype, &hash, &admin_signature, &0);
+
+    // Only the claiming user authorized the call
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, user);
+    assert_eq!(
+        client.get_wrap(&user, &202512).unwrap().archetype,
+        archetype
+    );
+}
+
+#[test]
+fn test_count_overflow_is_rejected() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::WrapCount(user.clone()), &u32::MAX);
+    });
+
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202512,
+        &archetype,
+        &hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap(&user, &202512, &archetype, &hash, &signature, &0, &None),
+        Err(Ok(ContractError::CountOverflow.into()))
+    );
+    assert_eq!(client.balance_of(&user), u32::MAX as i128);
+    assert!(client.get_wrap(&user, &202512).is_none());
+}
+
+#[test]
+fn test_alltime_wrap_is_mutable() {
+    let env = Env::default();
+    let (client, signing_key, admin) = setup(&env);
+    let user = Address::generate(&env);
+    let first_hash = BytesN::from_array(&env, &[1u8; 32]);
+    let second_hash = BytesN::from_array(&env, &[2u8; 32]);
+
+    client.update_alltime(&user, &symbol_short!("arch"), &first_hash);
+    client.update_alltime(&user, &symbol_short!("defi"), &second_hash);
+
+    let wrap = client.get_wrap(&user, &ALL_TIME_PERIOD).unwrap();
+    assert_eq!(wrap.archetype, symbol_short!("defi"));
+    assert_eq!(wrap.data_hash, second_hash);
+    assert_eq!(client.balance_of(&user), 1);
+
+    // Creating an all-time wrap is a mint, but restyling one is not
+    client.set_paused(&true);
+    assert_eq!(
+        client.try_update_alltime(
+            &Address::generate(&env),
+            &symbol_short!("arch"),
+            &first_hash
+        ),
+        Err(Ok(ContractError::Paused.into()))
+    );
+    client.update_alltime(&user, &symbol_short!("arch"), &first_hash);
+    client.set_paused(&false);
+    assert_eq!(
+        client.try_update_alltime(&admin, &symbol_short!("arch"), &first_hash),
+        Err(Ok(ContractError::SelfMintDisallowed.into()))
+    );
+    client.set_max_supply(&1);
+    assert_eq!(
+        client.try_update_alltime(
+            &Address::generate(&env),
+            &symbol_short!("arch"),
+            &first_hash
+        ),
+        Err(Ok(ContractError::MaxSupplyReached.into()))
+    );
+    client.set_max_supply(&0);
+    client.set_ledger_mint_cap(&1);
+    client.update_alltime(
+        &Address::generate(&env),
+        &symbol_short!("arch"),
+        &first_hash,
+    );
+    assert_eq!(
+        client.try_update_alltime(
+            &Address::generate(&env),
+            &symbol_short!("arch"),
+            &first_hash
+        ),
+        Err(Ok(ContractError::GlobalRateExceeded.into()))
+    );
+    client.set_ledger_mint_cap(&0);
+
+    // Regular periods stay write-once
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202512,
+        &symbol_short!("arch"),
+        &first_hash,
+    );
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202512,
+        &symbol_short!("defi"),
+        &second_hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap(
+            &user,
+            &202512,
+            &symbol_short!("defi"),
+            &second_hash,
+            &signature,
+            &0,
+            &None
+        ),
+        Err(Ok(ContractError::WrapAlreadyExists.into()))
+    );
+    assert_eq!(
+        client.list_periods(&user),
+        vec![&env, ALL_TIME_PERIOD, 202512]
+    );
+}
+
+#[test]
+fn test_verify_data_against_stored_hash() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+
+    let blob = Bytes::from_slice(&env, br#"{"persona":"architect","txs":42}"#);
+    let hash: BytesN<32> = env.crypto().sha256(&blob).to_bytes();
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202512,
+        &symbol_short!("arch"),
+        &hash,
+    );
+
+    let tampered = Bytes::from_slice(&env, br#"{"persona":"architect","txs":43}"#);
+    assert!(client.verify_data(&user, &202512, &blob));
+    assert!(!client.verify_data(&user, &202512, &tampered));
+    assert!(!client.verify_data(&user, &202601, &blob));
+}
+
+#[test]
+fn test_raw_count_reflects_stored_value() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+
+    assert_eq!(client.raw_count(&user), 0);
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202512,
+        &symbol_short!("arch"),
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+    assert_eq!(client.raw_count(&user), 1);
+
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::WrapCount(user.clone()), &7u32);
+    });
+    assert_eq!(client.raw_count(&user), 7);
+    assert_eq!(client.list_periods(&user).len(), 1);
+}
+
+#[test]
+fn test_mint_wrap_sym_normalizes_period() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[4u8; 32]);
+
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202401,
+        &archetype,
+        &hash,
+    );
+    client.mint_wrap_sym(
+        &user,
+        &symbol_short!("2024_01"),
+        &archetype,
+        &hash,
+        &signature,
+        &0,
+    );
+
+    let wrap = client.get_wrap(&user, &202401).unwrap();
+    assert_eq!(wrap.period, 202401);
+
+    assert_eq!(
+        client.try_mint_wrap_sym(
+            &user,
+            &symbol_short!("jan_2024"),
+            &archetype,
+            &hash,
+            &signature,
+            &0
+        ),
+        Err(Ok(ContractError::InvalidPeriod.into()))
+    );
+    assert_eq!(
+        client.try_mint_wrap_sym(
+            &user,
+            &symbol_short!("_"),
+            &archetype,
+            &hash,
+            &signature,
+            &0
+        ),
+        Err(Ok(ContractError::InvalidPeriod.into()))
+    );
+}
+
+#[test]
+fn test_hash_algo_tag() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[4u8; 32]);
+
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202511,
+        &archetype,
+        &hash,
+    );
+    assert_eq!(
+        client.get_hash_algo(&user, &202511),
+        Some(symbol_short!("sha256"))
+    );
+
+    // A holder can't retag a plain signature with another algorithm
+    let plain = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202512,
+        &archetype,
+        &hash,
+    );
+    assert!(client
+        .try_mint_wrap(
+            &user,
+            &202512,
+            &archetype,
+            &hash,
+            &plain,
+            &0,
+            &Some(symbol_short!("blake3"))
+        )
+        .is_err());
+
+    // Non-default algorithms are a signed term
+    let mut payload = mint_payload_bytes(&env, &client.address, &user, 202512, &archetype, &hash);
+    payload.append(&symbol_short!("algo").to_xdr(&env));
+    payload.append(&symbol_short!("blake3").to_xdr(&env));
+    let signature = sign_bytes(&env, &signing_key, &payload);
+    client.mint_wrap(
+        &user,
+        &202512,
+        &archetype,
+        &hash,
+        &signature,
+        &0,
+        &Some(symbol_short!("blake3")),
+    );
+    assert_eq!(
+        client.get_hash_algo(&user, &202512),
+        Some(symbol_short!("blake3"))
+    );
+    assert_eq!(client.get_hash_algo(&user, &202601), None);
+}
+
+#[test]
+fn test_recount_user_repairs_drift() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202511,
+        &archetype,
+        &hash,
+    );
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202512,
+        &archetype,
+        &hash,
+    );
+
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::WrapCount(user.clone()), &9u32);
+    });
+    assert_eq!(client.raw_count(&user), 9);
+
+    assert_eq!(client.recount_user(&user), 2);
+    assert_eq!(client.raw_count(&user), 2);
+    assert_eq!(client.balance_of(&user), 2);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    let topic: Symbol = topics.get(1).unwrap().try_into_val(&env).unwrap();
+    let (_, corrected): (u32, u32) = data.try_into_val(&env).unwrap();
+    assert_eq!(topic, symbol_short!("recount"));
+    assert_eq!(corrected, 2);
+}
+
+fn signed_entries(
+    env: &Env,
+    client: &StellarWrapContractClient,
+    signer: &SigningKey,
+    user: &Address,
+    count: u32,
+) -> Vec<MintEntry> {
+    let archetype = symbol_short!("arch");
+    let data_hash = BytesN::from_array(env, &[1u8; 32]);
+    let mut entries = Vec::new(env);
+    for i in 0..count {
+        let period = 200001 + i as u64;
+        let signature = sign_payload(
+            env,
+            signer,
+            &client.address,
+            user,
+            period,
+            &archetype,
+            &data_hash,
+        );
+        entries.push_back(MintEntry {
+            user: user.clone(),
+            period,
+            archetype: archetype.clone(),
+            data_hash: data_hash.clone(),
+            signature,
+            key_index: 0,
+        });
+    }
+    entries
+}
+
+#[test]
+fn test_batch_size_limit() {
+    let env = Env::default();
+    env.budget().reset_unlimited();
+    let (client, signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    let full = signed_entries(&env, &client, &signing_key, &user, MAX_BATCH);
+    client.mint_wrap_batch(&full);
+    assert_eq!(client.balance_of(&user), MAX_BATCH as i128);
+
+    let oversized = signed_entries(&env, &client, &signing_key, &other, MAX_BATCH + 1);
+    assert_eq!(
+        client.try_mint_wrap_batch(&oversized),
+        Err(Ok(ContractError::BatchTooLarge.into()))
+    );
+    assert_eq!(client.balance_of(&other), 0);
+}
+
+#[test]
+fn test_is_admin() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, StellarWrapContract);
+    let client = StellarWrapContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    assert!(!client.is_admin(&admin));
+
+    client.initialize(&admin, &BytesN::from_array(&env, &[1u8; 32]));
+    assert!(client.is_admin(&admin));
+    assert!(!client.is_admin(&stranger));
+}
+
+#[test]
+fn test_common_periods() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    for period in [202510u64, 202511, 202512] {
+        mint(
+            &env,
+            &client,
+            &signing_key,
+            &alice,
+            period,
+            &archetype,
+            &hash,
+        );
+    }
+    for period in [202512u64, 202509, 202510] {
+        mint(&env, &client, &signing_key, &bob, period, &archetype, &hash);
+    }
+
+    assert_eq!(
+        client.common_periods(&alice, &bob),
+        vec![&env, 202510u64, 202512]
+    );
+    assert_eq!(
+        client
+            .common_periods(&alice, &Address::generate(&env))
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_per_ledger_mint_cap() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    client.set_ledger_mint_cap(&2);
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &Address::generate(&env),
+        202512,
+        &archetype,
+        &hash,
+    );
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &Address::generate(&env),
+        202512,
+        &archetype,
+        &hash,
+    );
+
+    let user = Address::generate(&env);
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202512,
+        &archetype,
+        &hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap(&user, &202512, &archetype, &hash, &signature, &0, &None),
+        Err(Ok(ContractError::GlobalRateExceeded.into()))
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    client.mint_wrap(&user, &202512, &archetype, &hash, &signature, &0, &None);
+    assert_eq!(client.balance_of(&user), 1);
+}
+
+#[test]
+fn test_reset_config_preserves_wraps() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202512,
+        &symbol_short!("arch"),
+        &hash,
+    );
+    client.set_ledger_mint_cap(&1);
+
+    client.reset_config();
+
+    assert_eq!(client.get_admin(), None);
+    assert_eq!(client.list_signers().len(), 0);
+    assert_eq!(client.get_wrap(&user, &202512).unwrap().data_hash, hash);
+    assert_eq!(client.balance_of(&user), 1);
+
+    // The registry can be taken over by a fresh admin
+    let new_admin = Address::generate(&env);
+    client.initialize(&new_admin, &BytesN::from_array(&env, &[2u8; 32]));
+    assert_eq!(client.get_admin(), Some(new_admin));
+}
+
+#[test]
+fn test_mint_emits_sep41_balance_event() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202511,
+        &archetype,
+        &hash,
+    );
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202512,
+        &archetype,
+        &hash,
+    );
+
+    let balance = client.balance_of(&user);
+    let found = env.events().all().iter().any(|(contract, topics, data)| {
+        let expected_topics: Vec<soroban_sdk::Val> =
+            (symbol_short!("mint"), user.clone()).into_val(&env);
+        let amount: Result<i128, _> = data.try_into_val(&env);
+        contract == client.address && topics == expected_topics && amount == Ok(balance)
+    });
+    assert!(
+        found,
+        "expected a SEP-41 mint event carrying the new balance"
+    );
+}
+
+#[test]
+fn test_renounce_admin_locks_mutations() {
+    let env = Env::default();
+    let (client, signing_key, admin) = setup(&env);
+    let user = Address::generate(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202511,
+        &archetype,
+        &hash,
+    );
+    client.renounce_admin();
+
+    let renounced = Err(Ok(ContractError::AdminRenounced.into()));
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202512,
+        &archetype,
+        &hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap(&user, &202512, &archetype, &hash, &signature, &0, &None),
+        renounced
+    );
+    assert_eq!(client.try_update_admin(&user), renounced);
+    assert_eq!(
+        client.try_upgrade(&BytesN::from_array(&env, &[0u8; 32])),
+        renounced
+    );
+    assert_eq!(
+        client.try_initialize(&admin, &BytesN::from_array(&env, &[1u8; 32])),
+        renounced
+    );
+
+    // Reads are unaffected
+    assert_eq!(client.get_admin(), None);
+    assert_eq!(client.balance_of(&user), 1);
+    assert!(client.get_wrap(&user, &202511).is_some());
+}
+
+#[test]
+fn test_cosign_required_for_flagged_archetype() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let cosigner = SigningKey::from_bytes(&[8u8; 32]);
+    client.add_signing_key(&BytesN::from_array(
+        &env,
+        &cosigner.verifying_key().to_bytes(),
+    ));
+    let user = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let legend = symbol_short!("legend");
+
+    client.set_requires_cosign(&legend, &true);
+
+    // Unflagged archetypes still mint with a single signature
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202511,
+        &symbol_short!("arch"),
+        &hash,
+    );
+
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202512,
+        &legend,
+        &hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap(&user, &202512, &legend, &hash, &signature, &0, &None),
+        Err(Ok(ContractError::CosignRequired.into()))
+    );
+
+    let cosignature = sign_payload(
+        &env,
+        &cosigner,
+        &client.address,
+        &user,
+        202512,
+        &legend,
+        &hash,
+    );
+    client.mint_wrap_cosigned(&user, &202512, &legend, &hash, &signature, &0, &cosignature);
+    assert_eq!(client.get_wrap(&user, &202512).unwrap().archetype, legend);
+}
+
+#[test]
+fn test_first_and_latest_wrap() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    assert_eq!(client.first_wrap(&user), None);
+
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202512,
+        &symbol_short!("c"),
+        &hash,
+    );
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202503,
+        &symbol_short!("a"),
+        &hash,
+    );
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202507,
+        &symbol_short!("b"),
+        &hash,
+    );
+
+    let first = client.first_wrap(&user).unwrap();
+    assert_eq!(first.period, 202503);
+    assert_eq!(first.archetype, symbol_short!("a"));
+    assert_eq!(client.latest_wrap(&user).unwrap().period, 202512);
+
+    // The all-time wrap sorts lowest but is not the first membership wrap
+    client.update_alltime(&user, &symbol_short!("d"), &hash);
+    assert_eq!(client.first_wrap(&user).unwrap().period, 202503);
+
+    let alltime_only = Address::generate(&env);
+    client.update_alltime(&alltime_only, &symbol_short!("d"), &hash);
+    assert_eq!(client.first_wrap(&alltime_only), None);
+}
+
+#[test]
+fn test_wrap_id_is_deterministic() {
+    let env = Env::default();
+    let (client, _signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+
+    let id = client.wrap_id(&user, &202512);
+    assert_eq!(client.wrap_id(&user, &202512), id);
+    assert_ne!(client.wrap_id(&user, &202601), id);
+    assert_ne!(client.wrap_id(&Address::generate(&env), &202512), id);
+
+    // Matches the documented off-chain derivation
+    let mut preimage = Bytes::new(&env);
+    preimage.append(&client.address.clone().to_xdr(&env));
+    preimage.append(&user.clone().to_xdr(&env));
+    preimage.append(&202512u64.to_xdr(&env));
+    assert_eq!(env.crypto().sha256(&preimage).to_bytes(), id);
+}
+
+#[test]
+fn test_export_user_roundtrips_through_xdr() {
+    use soroban_sdk::xdr::FromXdr;
+
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202512,
+        &symbol_short!("arch"),
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202511,
+        &symbol_short!("defi"),
+        &BytesN::from_array(&env, &[2u8; 32]),
+    );
+
+    let exported = client.export_user(&user);
+    let decoded = Vec::<WrapRecord>::from_xdr(&env, &exported).unwrap();
+    assert_eq!(
+        decoded,
+        vec![
+            &env,
+            client.get_wrap(&user, &202511).unwrap(),
+            client.get_wrap(&user, &202512).unwrap()
+        ]
+    );
+}
+
+#[test]
+fn test_conditional_mint_requires_prerequisite() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    // The prerequisite is one of the signed terms
+    let mut payload = mint_payload_bytes(&env, &client.address, &user, 202512, &archetype, &hash);
+    payload.append(&symbol_short!("requires").to_xdr(&env));
+    payload.append(&202511u64.to_xdr(&env));
+    let signature = sign_bytes(&env, &signing_key, &payload);
+
+    assert_eq!(
+        client.try_mint_wrap_conditional(
+            &user,
+            &202512,
+            &archetype,
+            &hash,
+            &signature,
+            &0,
+            &Some(202511)
+        ),
+        Err(Ok(ContractError::PrerequisiteMissing.into()))
+    );
+
+    // Dropping or changing the prerequisite doesn't match the signature
+    assert!(client
+        .try_mint_wrap(&user, &202512, &archetype, &hash, &signature, &0, &None)
+        .is_err());
+    assert!(client
+        .try_mint_wrap_conditional(&user, &202512, &archetype, &hash, &signature, &0, &None)
+        .is_err());
+    assert!(client
+        .try_mint_wrap_conditional(
+            &user,
+            &202512,
+            &archetype,
+            &hash,
+            &signature,
+            &0,
+            &Some(202510)
+        )
+        .is_err());
+    assert_eq!(client.get_wrap(&user, &202512), None);
+
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202511,
+        &archetype,
+        &hash,
+    );
+    client.mint_wrap_conditional(
+        &user,
+        &202512,
+        &archetype,
+        &hash,
+        &signature,
+        &0,
+        &Some(202511),
+    );
+    assert!(client.get_wrap(&user, &202512).is_some());
+}
+
+#[test]
+fn test_revoke_period_across_users() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &alice,
+        202512,
+        &archetype,
+        &hash,
+    );
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &alice,
+        202511,
+        &archetype,
+        &hash,
+    );
+    mint(&env, &client, &signing_key, &bob, 202512, &archetype, &hash);
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &carol,
+        202511,
+        &archetype,
+        &hash,
+    );
+
+    let revoked = client.revoke_period(
+        &202512,
+        &vec![&env, alice.clone(), bob.clone(), carol.clone()],
+    );
+    assert_eq!(revoked, 2);
+
+    assert_eq!(client.balance_of(&alice), 1);
+    assert_eq!(client.balance_of(&bob), 0);
+    assert_eq!(client.balance_of(&carol), 1);
+    assert!(client.get_wrap(&alice, &202512).is_none());
+    assert_eq!(client.list_periods(&alice), vec![&env, 202511u64]);
+
+    let revoke_events = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            let topic: Result<Symbol, _> = topics.get(1).unwrap().try_into_val(&env);
+            topic == Ok(symbol_short!("revoke"))
+        })
+        .count();
+    assert_eq!(revoke_events, 2);
+}
+
+#[test]
+fn test_mint_start_ledger() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+    client.set_mint_start_ledger(&150);
+
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202512,
+        &archetype,
+        &hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap(&user, &202512, &archetype, &hash, &signature, &0, &None),
+        Err(Ok(ContractError::MintNotStarted.into()))
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number = 150);
+    client.mint_wrap(&user, &202512, &archetype, &hash, &signature, &0, &None);
+    assert_eq!(client.balance_of(&user), 1);
+}
+
+#[test]
+fn test_minted_by_tallies_per_minter() {
+    let env = Env::default();
+    let (client, signing_key, admin) = setup(&env);
+    let minter = Address::generate(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    client.set_minter(&minter);
+
+    // Minted directly by the admin and through an admin signature
+    client.mint_by(&admin, &Address::generate(&env), &202512, &archetype, &hash);
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &Address::generate(&env),
+        202512,
+        &archetype,
+        &hash,
+    );
+
+    // Minted by the designated minter
+    client.mint_by(
+        &minter,
+        &Address::generate(&env),
+        &202512,
+        &archetype,
+        &hash,
+    );
+
+    assert_eq!(client.minted_by(&admin), 2);
+    assert_eq!(client.minted_by(&minter), 1);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_mint_by(
+            &stranger,
+            &Address::generate(&env),
+            &202512,
+            &archetype,
+            &hash
+        ),
+        Err(Ok(ContractError::Unauthorized.into()))
+    );
+    assert_eq!(client.minted_by(&stranger), 0);
+}
+
+#[test]
+fn test_storage_stats() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &alice,
+        202511,
+        &archetype,
+        &hash,
+    );
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &alice,
+        202512,
+        &archetype,
+        &hash,
+    );
+    mint(&env, &client, &signing_key, &bob, 202512, &archetype, &hash);
+
+    assert_eq!(
+        client.storage_stats(),
+        StorageStats {
+            total_supply: 3,
+            holder_count: 2,
+            distinct_periods: 2,
+        }
+    );
+
+    // Revocations shrink supply and holders but periods were still seen
+    client.revoke_period(&202512, &vec![&env, bob.clone()]);
+    let stats = client.storage_stats();
+    assert_eq!(stats.total_supply, 2);
+    assert_eq!(stats.holder_count, 1);
+    assert_eq!(stats.distinct_periods, 2);
+}
+
+#[test]
+fn test_idempotent_mint_retry() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let user = Address::generate(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let other_hash = BytesN::from_array(&env, &[2u8; 32]);
+
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202512,
+        &archetype,
+        &hash,
+    );
+    client.mint_wrap_idempotent(&user, &202512, &archetype, &hash, &signature, &0);
+
+    // Retrying the identical mint is a no-op
+    assert_eq!(
+        client.try_mint_wrap_idempotent(&user, &202512, &archetype, &hash, &signature, &0),
+        Ok(Ok(()))
+    );
+    assert_eq!(client.balance_of(&user), 1);
+
+    // A conflicting hash for the same period still fails
+    let conflicting = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202512,
+        &archetype,
+        &other_hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap_idempotent(&user, &202512, &archetype, &other_hash, &conflicting, &0),
+        Err(Ok(ContractError::WrapAlreadyExists))
+    );
+    assert_eq!(client.get_wrap(&user, &202512).unwrap().data_hash, hash);
+}
+
+#[test]
+fn test_is_initialized() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, StellarWrapContract);
+    let client = StellarWrapContractClient::new(&env, &contract_id);
+    assert!(!client.is_initialized());
+
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    let admin_pubkey = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+    client.initialize(&Address::generate(&env), &admin_pubkey);
+    assert!(client.is_initialized());
+}
+
+#[test]
+fn test_migrate_v1_to_v2() {
+    use crate::storage_types::{DataKey, LegacyDataKey, LegacyWrapRecord};
+
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[6u8; 32]);
+    let legacy_periods = [symbol_short!("2024_01"), symbol_short!("2024_02")];
+
+    // A fresh deployment is already on the current layout
+    assert_eq!(
+        client.try_finalize_migration(),
+        Err(Ok(ContractError::AlreadyMigrated.into()))
+    );
+
+    // Inject records the way a v1 deployment stored them
+    env.as_contract(&client.address, || {
+        env.storage().instance().remove(&DataKey::SchemaVersion);
+        for legacy_period in legacy_periods.iter() {
+            env.storage().persistent().set(
+                &LegacyDataKey::Wrap(user.clone(), legacy_period.clone()),
+                &LegacyWrapRecord {
+                    timestamp: 1_700_000_000,
+                    data_hash: hash.clone(),
+                    archetype: symbol_short!("builder"),
+                    period: legacy_period.clone(),
+                },
+            );
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::WrapCount(user.clone()), &2u32);
+    });
+    assert!(client.get_wrap(&user, &202401).is_none());
+
+    // Pages are bounded like every other batch call
+    let mut oversized = Vec::new(&env);
+    for _ in 0..=MAX_BATCH {
+        oversized.push_back((user.clone(), legacy_periods[0].clone()));
+    }
+    assert_eq!(
+        client.try_migrate_v1_to_v2(&oversized),
+        Err(Ok(ContractError::BatchTooLarge.into()))
+    );
+
+    // Two pages; the schema stays at v1 until finalized
+    let first = vec![&env, (user.clone(), legacy_periods[0].clone())];
+    let second = vec![&env, (user.clone(), legacy_periods[1].clone())];
+    assert_eq!(client.migrate_v1_to_v2(&first), 1);
+    assert_eq!(client.migrate_v1_to_v2(&second), 1);
+
+    let record = client.get_wrap(&user, &202401).unwrap();
+    assert_eq!(record.period, 202401);
+    assert_eq!(record.data_hash, hash);
+    assert_eq!(record.timestamp, 1_700_000_000);
+    assert_eq!(record.hash_algo, symbol_short!("sha256"));
+    assert_eq!(record.ledger_seq, 0);
+    assert_eq!(client.balance_of(&user), 2);
+    assert_eq!(client.list_periods(&user), vec![&env, 202401u64, 202402]);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&LegacyDataKey::Wrap(
+            user.clone(),
+            legacy_periods[0].clone()
+        )));
+    });
+
+    // Migrated wraps count in the contract-wide stats without double-counting the holder
+    let stats = client.storage_stats();
+    assert_eq!(stats.total_supply, 2);
+    assert_eq!(stats.holder_count, 1);
+    assert_eq!(stats.distinct_periods, 2);
+    assert_eq!(client.archetype_count(&symbol_short!("builder")), 2);
+    assert_eq!(
+        client.list_archetypes(),
+        vec![&env, symbol_short!("builder")]
+    );
+    assert_eq!(client.top_holders(&1), vec![&env, (user.clone(), 2u32)]);
+    assert_eq!(client.period_total(&202401), 1);
+
+    // A user already holding the numeric period keeps both records and their count
+    let other = Address::generate(&env);
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &other,
+        202401,
+        &symbol_short!("builder"),
+        &hash,
+    );
+    let legacy_key = LegacyDataKey::Wrap(other.clone(), legacy_periods[0].clone());
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(
+            &legacy_key,
+            &LegacyWrapRecord {
+                timestamp: 1_700_000_000,
+                data_hash: hash.clone(),
+                archetype: symbol_short!("builder"),
+                period: legacy_periods[0].clone(),
+            },
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::WrapCount(other.clone()), &2u32);
+    });
+    let clash = vec![&env, (other.clone(), legacy_periods[0].clone())];
+    assert_eq!(client.migrate_v1_to_v2(&clash), 0);
+    assert_eq!(client.raw_count(&other), 2);
+    env.as_contract(&client.address, || {
+        assert!(env.storage().persistent().has(&legacy_key));
+    });
+
+    // Finalizing closes the migration
+    client.finalize_migration();
+    assert_eq!(
+        client.try_migrate_v1_to_v2(&first),
+        Err(Ok(ContractError::AlreadyMigrated.into()))
+    );
+    assert_eq!(
+        client.try_finalize_migration(),
+        Err(Ok(ContractError::AlreadyMigrated.into()))
+    );
+}
+
+#[test]
+fn test_minter_two_step_handoff() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+    let minter = Address::generate(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    client.propose_minter(&minter);
+    // Not a minter until the proposal is accepted
+    assert_eq!(
+        client.try_mint_by(
+            &minter,
+            &Address::generate(&env),
+            &202512,
+            &archetype,
+            &hash
+        ),
+        Err(Ok(ContractError::Unauthorized.into()))
+    );
+
+    client.accept_minter();
+    client.mint_by(
+        &minter,
+        &Address::generate(&env),
+        &202512,
+        &archetype,
+        &hash,
+    );
+    assert_eq!(client.minted_by(&minter), 1);
+
+    // Nothing left to accept
+    assert_eq!(
+        client.try_accept_minter(),
+        Err(Ok(ContractError::Unauthorized.into()))
+    );
+}
+
+#[test]
+fn test_accept_minter_by_wrong_address_fails() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+    let minter = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    client.propose_minter(&minter);
+
+    // Only the stranger signs, so the proposed minter's auth is missing
+    env.mock_auths(&[MockAuth {
+        address: &stranger,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "accept_minter",
+            args: ().into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_accept_minter().is_err());
+}
+
+#[test]
+fn test_description() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+    assert_eq!(client.description(), String::from_str(&env, ""));
+
+    let description = String::from_str(&env, "Monthly on-chain activity recaps for Stellar users");
+    client.set_description(&description);
+    assert_eq!(client.description(), description);
+}
+
+#[test]
+fn test_period_total() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    mint(&env, &client, &signing_key, &alice, 202512, &arch, &hash);
+    mint(&env, &client, &signing_key, &bob, 202512, &arch, &hash);
+    mint(&env, &client, &signing_key, &alice, 202601, &arch, &hash);
+    // Each user holds at most one wrap per period, so the third goes to a new holder
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &Address::generate(&env),
+        202512,
+        &arch,
+        &hash,
+    );
+
+    assert_eq!(client.period_total(&202512), 3);
+    assert_eq!(client.period_total(&202601), 1);
+    assert_eq!(client.period_total(&202602), 0);
+}
+
+#[test]
+fn test_revoke_wrap_middle_period_keeps_index_sorted() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    for period in 1..=3u64 {
+        mint(&env, &client, &signing_key, &user, period, &arch, &hash);
+    }
+
+    assert!(client.revoke_wrap(&user, &2));
+    assert_eq!(client.list_periods(&user), vec![&env, 1u64, 3u64]);
+    assert!(client.get_wrap(&user, &2).is_none());
+    assert_eq!(client.balance_of(&user), 2);
+
+    // Revoking again is a no-op
+    assert!(!client.revoke_wrap(&user, &2));
+}
+
+#[test]
+fn test_claim_wrap_charges_mint_fee() {
+    let env = Env::default();
+    let (client, signing_key, admin) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    let fee_token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let token_client = soroban_sdk::token::Client::new(&env, &fee_token.address());
+    soroban_sdk::token::StellarAssetClient::new(&env, &fee_token.address()).mint(&user, &100);
+
+    client.set_mint_fee(&fee_token.address(), &30);
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202512,
+        &arch,
+        &hash,
+    );
+    client.claim_wrap(&user, &202512, &arch, &hash, &signature, &0);
+
+    assert_eq!(token_client.balance(&user), 70);
+    assert_eq!(token_client.balance(&admin), 30);
+    assert!(client.get_wrap(&user, &202512).is_some());
+
+    // A zero fee disables charging
+    client.set_mint_fee(&fee_token.address(), &0);
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202601,
+        &arch,
+        &hash,
+    );
+    client.claim_wrap(&user, &202601, &arch, &hash, &signature, &0);
+    assert_eq!(token_client.balance(&user), 70);
+
+    assert_eq!(
+        client.try_set_mint_fee(&fee_token.address(), &-1),
+        Err(Ok(ContractError::InvalidFee.into()))
+    );
+}
+
+#[test]
+fn test_wrap_lifetime_expires_balance() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.set_wrap_lifetime(&100);
+    mint(&env, &client, &signing_key, &user, 202512, &arch, &hash);
+    env.ledger().with_mut(|li| li.timestamp = 1_050);
+    mint(&env, &client, &signing_key, &user, 202601, &arch, &hash);
+
+    assert_eq!(client.balance_of(&user), 2);
+    assert!(!client.is_expired(&user, &202512));
+
+    // Past the first wrap's expiry but not the second's
+    env.ledger().with_mut(|li| li.timestamp = 1_100);
+    assert_eq!(client.balance_of(&user), 1);
+    assert_eq!(client.get_profile(&user).count, 1);
+    assert!(client.is_expired(&user, &202512));
+    assert!(!client.is_expired(&user, &202601));
+    assert!(!client.is_expired(&user, &202602));
+
+    // Disabling the lifetime counts every wrap again
+    client.set_wrap_lifetime(&0);
+    assert_eq!(client.balance_of(&user), 2);
+}
+
+#[test]
+fn test_archetype_histogram() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let builder = symbol_short!("builder");
+    let trader = symbol_short!("trader");
+    let users = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &users.get(0).unwrap(),
+        202512,
+        &builder,
+        &hash,
+    );
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &users.get(1).unwrap(),
+        202512,
+        &trader,
+        &hash,
+    );
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &users.get(2).unwrap(),
+        202512,
+        &builder,
+        &hash,
+    );
+    // Holds a wrap for another period only, so it is not counted
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &users.get(3).unwrap(),
+        202601,
+        &trader,
+        &hash,
+    );
+
+    assert_eq!(
+        client.archetype_histogram(&users, &202512),
+        vec![&env, (builder, 2u32), (trader, 1u32)]
+    );
+
+    let mut too_many = Vec::new(&env);
+    for _ in 0..=MAX_BATCH {
+        too_many.push_back(Address::generate(&env));
+    }
+    assert_eq!(
+        client.try_archetype_histogram(&too_many, &202512),
+        Err(Ok(ContractError::BatchTooLarge.into()))
+    );
+}
+
+#[test]
+fn test_get_wrap_or_err() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    mint(&env, &client, &signing_key, &user, 202512, &arch, &hash);
+
+    let record = client.try_get_wrap_or_err(&user, &202512).unwrap().unwrap();
+    assert_eq!(record, client.get_wrap(&user, &202512).unwrap());
+    assert_eq!(
+        client.try_get_wrap_or_err(&user, &202601),
+        Err(Ok(ContractError::WrapNotFound))
+    );
+}
+
+#[test]
+fn test_global_and_period_count_overflow_is_rejected() {
+    let env = Env::default();
+    let (client, signing_key, _admin) = setup(&env);
+    let archetype = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    let user = Address::generate(&env);
+    env.as_contract(&client.address, || {
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalSupply, &u32::MAX);
+    });
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202512,
+        &archetype,
+        &hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap(&user, &202512, &archetype, &hash, &signature, &0, &None),
+        Err(Ok(ContractError::CountOverflow.into()))
+    );
+
+    let user = Address::generate(&env);
+    env.as_contract(&client.address, || {
+        env.storage().instance().set(&DataKey::TotalSupply, &0u32);
+        env.storage()
+            .persistent()
+            .set(&DataKey::PeriodMinted(202512), &u32::MAX);
+    });
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202512,
+        &archetype,
+        &hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap(&user, &202512, &archetype, &hash, &signature, &0, &None),
+        Err(Ok(ContractError::CountOverflow.into()))
+    );
+    assert_eq!(client.period_total(&202512), u32::MAX);
+    assert!(client.get_wrap(&user, &202512).is_none());
+}
+
+#[test]
+fn test_milestone_events() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    client.set_milestones(&vec![&env, 2u32, 5u32]);
+
+    // Milestone values of every milestn event emitted so far
+    let milestones = |env: &Env| -> Vec<u32> {
+        let topics: Vec<soroban_sdk::Val> = (
+            symbol_short!("wrap"),
+            symbol_short!("milestn"),
+            user.clone(),
+        )
+            .into_val(env);
+        let mut values = Vec::new(env);
+        for (_, t, data) in env.events().all().iter() {
+            if t == topics {
+                let (_, milestone): (u32, u32) = data.try_into_val(env).unwrap();
+                values.push_back(milestone);
+            }
+        }
+        values
+    };
+
+    mint(&env, &client, &signing_key, &user, 1, &arch, &hash);
+    assert_eq!(milestones(&env), Vec::new(&env));
+    mint(&env, &client, &signing_key, &user, 2, &arch, &hash);
+    assert_eq!(milestones(&env), vec![&env, 2u32]);
+    mint(&env, &client, &signing_key, &user, 3, &arch, &hash);
+    assert_eq!(milestones(&env), vec![&env, 2u32]);
+    mint(&env, &client, &signing_key, &user, 4, &arch, &hash);
+    assert_eq!(milestones(&env), vec![&env, 2u32]);
+    mint(&env, &client, &signing_key, &user, 5, &arch, &hash);
+    assert_eq!(milestones(&env), vec![&env, 2u32, 5u32]);
+}
+
+#[test]
+fn test_list_archetypes_dedups() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    assert_eq!(client.list_archetypes(), Vec::new(&env));
+
+    for archetype in [
+        symbol_short!("architect"),
+        symbol_short!("defi"),
+        symbol_short!("architect"),
+    ] {
+        mint(
+            &env,
+            &client,
+            &signing_key,
+            &Address::generate(&env),
+            202512,
+            &archetype,
+            &hash,
+        );
+    }
+
+    assert_eq!(
+        client.list_archetypes(),
+        vec![&env, symbol_short!("architect"), symbol_short!("defi")]
+    );
+}
+
+#[test]
+fn test_get_config() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+
+    let config = client.get_config();
+    assert_eq!(config.mint_start, 0);
+    assert_eq!(config.ledger_mint_cap, 0);
+    assert!(!config.allow_self_mint);
+    assert_eq!(config.minter, None);
+
+    let minter = Address::generate(&env);
+    client.set_mint_start_ledger(&500);
+    client.set_ledger_mint_cap(&3);
+    client.set_wrap_lifetime(&86_400);
+    client.set_minter(&minter);
+
+    let config = client.get_config();
+    assert_eq!(config.mint_start, 500);
+    assert_eq!(config.ledger_mint_cap, 3);
+    assert_eq!(config.wrap_lifetime, 86_400);
+    assert_eq!(config.minter, Some(minter));
+    assert!(!config.allow_self_mint);
+}
+
+/// Stand-in for an automation contract that mints on a schedule
+mod automation {
+    use crate::StellarWrapContractClient;
+    use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Symbol};
+
+    #[contract]
+    pub struct Automation;
+
+    #[contractimpl]
+    impl Automation {
+        pub fn run(
+            e: Env,
+            registry: Address,
+            user: Address,
+            period: u64,
+            archetype: Symbol,
+            data_hash: BytesN<32>,
+        ) {
+            StellarWrapContractClient::new(&e, &registry).mint_by(
+                &e.current_contract_address(),
+                &user,
+                &period,
+                &archetype,
+                &data_hash,
+            );
+        }
+    }
+}
+
+#[test]
+fn test_contract_minter_mints_via_cross_contract_call() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+    let automation_id = env.register_contract(None, automation::Automation);
+    let automation = automation::AutomationClient::new(&env, &automation_id);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    client.set_minter(&automation_id);
+
+    // Stop mocking so the minter's auth has to come from the call itself
+    env.set_auths(&[]);
+    automation.run(&client.address, &user, &202512, &arch, &hash);
+
+    assert_eq!(client.get_wrap(&user, &202512).unwrap().data_hash, hash);
+    assert_eq!(client.minted_by(&automation_id), 1);
+}
+
+#[test]
+fn test_get_profile() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202510,
+        &symbol_short!("builder"),
+        &hash,
+    );
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202512,
+        &symbol_short!("trader"),
+        &hash,
+    );
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202511,
+        &symbol_short!("builder"),
+        &hash,
+    );
+
+    let profile = client.get_profile(&user);
+    assert_eq!(profile.count as i128, client.balance_of(&user));
+    assert_eq!(
+        profile.latest_period,
+        client.latest_wrap(&user).map(|record| record.period)
+    );
+    assert_eq!(profile.latest_period, Some(202512));
+    assert_eq!(profile.distinct_archetypes, 2);
+    assert_eq!(profile.total_score, 3);
+
+    let empty = client.get_profile(&Address::generate(&env));
+    assert_eq!(empty.count, 0);
+    assert_eq!(empty.latest_period, None);
+    assert_eq!(empty.distinct_archetypes, 0);
+}
+
+#[test]
+fn test_eligibility_allowlist() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let eligible = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    client.add_eligible(&eligible);
+    client.set_eligibility_enforced(&true);
+
+    mint(&env, &client, &signing_key, &eligible, 202512, &arch, &hash);
+    assert!(client.get_wrap(&eligible, &202512).is_some());
+
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &outsider,
+        202512,
+        &arch,
+        &hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap(&outsider, &202512, &arch, &hash, &signature, &0, &None),
+        Err(Ok(ContractError::NotEligible.into()))
+    );
+
+    // Removed recipients are rejected again
+    client.remove_eligible(&eligible);
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &eligible,
+        202601,
+        &arch,
+        &hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap(&eligible, &202601, &arch, &hash, &signature, &0, &None),
+        Err(Ok(ContractError::NotEligible.into()))
+    );
+
+    client.set_eligibility_enforced(&false);
+    mint(&env, &client, &signing_key, &outsider, 202512, &arch, &hash);
+    assert!(client.get_wrap(&outsider, &202512).is_some());
+}
+
+#[test]
+fn test_bump_wrap_ttl_emits_event() {
+    use soroban_sdk::testutils::storage::Persistent as _;
+
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    mint(&env, &client, &signing_key, &user, 202512, &arch, &hash);
+
+    let extension = 17280 * 400;
+    env.ledger().with_mut(|li| li.max_entry_ttl = extension + 1);
+    client.bump_wrap_ttl(&user, &202512, &extension);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (
+            symbol_short!("wrap"),
+            symbol_short!("ttl_bump"),
+            user.clone(),
+            202512u64
+        )
+            .into_val(&env)
+    );
+    let (_, ledgers): (u32, u32) = data.try_into_val(&env).unwrap();
+    assert_eq!(ledgers, extension);
+
+    env.as_contract(&client.address, || {
+        let ttl = env
+            .storage()
+            .persistent()
+            .get_ttl(&DataKey::Wrap(user.clone(), 202512));
+        assert_eq!(ttl, extension);
+    });
+
+    assert_eq!(
+        client.try_bump_wrap_ttl(&user, &202601, &extension),
+        Err(Ok(ContractError::WrapNotFound.into()))
+    );
+}
+
+#[test]
+fn test_top_holders_ordering() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    for (user, count) in [(&alice, 2u64), (&bob, 3), (&carol, 1)] {
+        for period in 1..=count {
+            mint(&env, &client, &signing_key, user, period, &arch, &hash);
+        }
+    }
+
+    assert_eq!(
+        client.top_holders(&10),
+        vec![
+            &env,
+            (bob.clone(), 3u32),
+            (alice.clone(), 2u32),
+            (carol.clone(), 1u32)
+        ]
+    );
+    assert_eq!(client.top_holders(&1), vec![&env, (bob.clone(), 3u32)]);
+
+    // Revoking re-sorts the board and an emptied holder drops off
+    client.revoke_wrap(&bob, &1);
+    client.revoke_wrap(&bob, &2);
+    client.revoke_wrap(&carol, &1);
+    assert_eq!(
+        client.top_holders(&10),
+        vec![&env, (alice, 2u32), (bob, 1u32)]
+    );
+}
+
+#[test]
+fn test_wrap_records_mint_ledger_sequence() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    env.ledger().with_mut(|li| li.sequence_number = 142);
+    mint(&env, &client, &signing_key, &user, 202512, &arch, &hash);
+
+    assert_eq!(client.get_wrap(&user, &202512).unwrap().ledger_seq, 142);
+}
+
+#[test]
+fn test_verify_mint_signature() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202512,
+        &arch,
+        &hash,
+    );
+
+    assert!(client.verify_mint_signature(&user, &202512, &arch, &hash, &signature, &0));
+
+    // Altering any signed field makes verification fail
+    let other_hash = BytesN::from_array(&env, &[2u8; 32]);
+    let other_user = Address::generate(&env);
+    assert!(!client.verify_mint_signature(&other_user, &202512, &arch, &hash, &signature, &0));
+    assert!(!client.verify_mint_signature(&user, &202601, &arch, &hash, &signature, &0));
+    assert!(!client.verify_mint_signature(
+        &user,
+        &202512,
+        &symbol_short!("other"),
+        &hash,
+        &signature,
+        &0
+    ));
+    assert!(!client.verify_mint_signature(&user, &202512, &arch, &other_hash, &signature, &0));
+
+    // Verifying has no side effects, and a spent signature reports false
+    assert!(client.get_wrap(&user, &202512).is_none());
+    client.mint_wrap(&user, &202512, &arch, &hash, &signature, &0, &None);
+    assert!(!client.verify_mint_signature(&user, &202512, &arch, &hash, &signature, &0));
+}
+
+#[test]
+fn test_is_valid_mint_auth() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202512,
+        &arch,
+        &hash,
+    );
+
+    assert!(client.is_valid_mint_auth(&user, &202512, &arch, &hash, &signature));
+    assert!(!client.is_valid_mint_auth(&user, &202601, &arch, &hash, &signature));
+    assert!(!client.is_valid_mint_auth(
+        &user,
+        &202512,
+        &arch,
+        &BytesN::from_array(&env, &[2u8; 32]),
+        &signature
+    ));
+
+    // Any live key counts, and a removed key no longer does
+    let new_key = SigningKey::from_bytes(&[9u8; 32]);
+    client.add_signing_key(&BytesN::from_array(
+        &env,
+        &new_key.verifying_key().to_bytes(),
+    ));
+    let rotated = sign_payload(&env, &new_key, &client.address, &user, 202601, &arch, &hash);
+    assert!(client.is_valid_mint_auth(&user, &202601, &arch, &hash, &rotated));
+    client.remove_signing_key(&BytesN::from_array(
+        &env,
+        &signing_key.verifying_key().to_bytes(),
+    ));
+    assert!(!client.is_valid_mint_auth(&user, &202512, &arch, &hash, &signature));
+
+    // A spent signature still reports the authorization, and nothing was written first
+    assert!(client.get_wrap(&user, &202601).is_none());
+    client.mint_wrap(&user, &202601, &arch, &hash, &rotated, &1, &None);
+    assert!(client.is_valid_mint_auth(&user, &202601, &arch, &hash, &rotated));
+}
+
+#[test]
+fn test_archetype_label() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+    let label = String::from_str(&env, "The Architect");
+
+    client.set_archetype_label(&symbol_short!("architect"), &label);
+    assert_eq!(
+        client.archetype_label(&symbol_short!("architect")),
+        Some(label)
+    );
+    assert_eq!(client.archetype_label(&symbol_short!("defi")), None);
+}
+
+/// Stellar account address for an ed25519 key, built from its ScVal XDR encoding
+fn account_address(env: &Env, key: &SigningKey) -> Address {
+    use soroban_sdk::xdr::FromXdr;
+
+    let mut xdr = Bytes::from_array(env, &[0, 0, 0, 18, 0, 0, 0, 0, 0, 0, 0, 0]);
+    xdr.extend_from_array(&key.verifying_key().to_bytes());
+    Address::from_xdr(env, &xdr).unwrap()
+}
+
+#[test]
+fn test_mint_wrap_with_consent() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let recipient_key = SigningKey::from_bytes(&[9u8; 32]);
+    let user = account_address(&env, &recipient_key);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202512,
+        &arch,
+        &hash,
+    );
+    let wrap_id = client.wrap_id(&user, &202512);
+    let consent = BytesN::from_array(&env, &recipient_key.sign(&wrap_id.to_array()).to_bytes());
+    client.mint_wrap_with_consent(&user, &202512, &arch, &hash, &signature, &0, &consent);
+    assert!(client.get_wrap(&user, &202512).unwrap().consented);
+
+    // Plain mints are not marked as consented
+    mint(&env, &client, &signing_key, &user, 202601, &arch, &hash);
+    assert!(!client.get_wrap(&user, &202601).unwrap().consented);
+
+    // A consent signature from someone other than the recipient is rejected
+    let impostor = SigningKey::from_bytes(&[10u8; 32]);
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202602,
+        &arch,
+        &hash,
+    );
+    let wrap_id = client.wrap_id(&user, &202602);
+    let forged = BytesN::from_array(&env, &impostor.sign(&wrap_id.to_array()).to_bytes());
+    assert_eq!(
+        client.try_mint_wrap_with_consent(&user, &202602, &arch, &hash, &signature, &0, &forged),
+        Err(Ok(ContractError::ConsentInvalid.into()))
+    );
+    assert!(client.get_wrap(&user, &202602).is_none());
+
+    // Contract addresses have no key to consent with
+    let contract_user = Address::generate(&env);
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &contract_user,
+        202512,
+        &arch,
+        &hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap_with_consent(
+            &contract_user,
+            &202512,
+            &arch,
+            &hash,
+            &signature,
+            &0,
+            &consent
+        ),
+        Err(Ok(ContractError::ConsentInvalid.into()))
+    );
+}
+
+#[test]
+fn test_recent_mints_keeps_last_twenty() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    for period in 1..=25u64 {
+        mint(&env, &client, &signing_key, &user, period, &arch, &hash);
+    }
+
+    let recent = client.recent_mints();
+    assert_eq!(recent.len(), 20);
+    assert_eq!(recent.first().unwrap(), (user.clone(), 6u64));
+    assert_eq!(recent.last().unwrap(), (user, 25u64));
+}
+
+#[test]
+fn test_get_profiles_matches_individual_profiles() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let users = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+
+    let first = users.get(0).unwrap();
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &first,
+        202511,
+        &symbol_short!("builder"),
+        &hash,
+    );
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &first,
+        202512,
+        &symbol_short!("trader"),
+        &hash,
+    );
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &users.get(1).unwrap(),
+        202601,
+        &symbol_short!("builder"),
+        &hash,
+    );
+
+    let profiles = client.get_profiles(&users);
+    assert_eq!(profiles.len(), 3);
+    for (user, profile) in users.iter().zip(profiles.iter()) {
+        assert_eq!(profile, client.get_profile(&user));
+    }
+    assert_eq!(profiles.get(2).unwrap().count, 0);
+
+    let mut too_many = Vec::new(&env);
+    for _ in 0..=MAX_BATCH {
+        too_many.push_back(Address::generate(&env));
+    }
+    assert_eq!(
+        client.try_get_profiles(&too_many),
+        Err(Ok(ContractError::BatchTooLarge.into()))
+    );
+}
+
+#[test]
+fn test_initialize_rejects_all_zero_pubkey() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, StellarWrapContract);
+    let client = StellarWrapContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    assert_eq!(
+        client.try_initialize(&admin, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(ContractError::InvalidPubKey.into()))
+    );
+    assert!(!client.is_initialized());
+
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    client.initialize(
+        &admin,
+        &BytesN::from_array(&env, &signing_key.verifying_key().to_bytes()),
+    );
+    assert!(client.is_initialized());
+
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_add_signing_key(&BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(ContractError::InvalidPubKey.into()))
+    );
+}
+
+#[test]
+fn test_is_paused() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    assert!(!client.is_paused());
+
+    client.set_paused(&true);
+    assert!(client.is_paused());
+    assert!(client.get_config().paused);
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202512,
+        &arch,
+        &hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap(&user, &202512, &arch, &hash, &signature, &0, &None),
+        Err(Ok(ContractError::Paused.into()))
+    );
+
+    client.set_paused(&false);
+    assert!(!client.is_paused());
+    client.mint_wrap(&user, &202512, &arch, &hash, &signature, &0, &None);
+}
+
+#[test]
+fn test_rename_archetype() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let architect = symbol_short!("architect");
+    let builder = symbol_short!("builder");
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &alice,
+        202512,
+        &architect,
+        &hash,
+    );
+    mint(&env, &client, &signing_key, &bob, 202512, &architect, &hash);
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &bob,
+        202601,
+        &symbol_short!("defi"),
+        &hash,
+    );
+    assert_eq!(client.archetype_count(&architect), 2);
+
+    // bob's 202601 wrap is listed but is not an architect, so it is left alone
+    let affected = vec![
+        &env,
+        (alice.clone(), 202512u64),
+        (bob.clone(), 202512u64),
+        (bob.clone(), 202601u64),
+    ];
+    assert_eq!(client.rename_archetype(&architect, &builder, &affected), 2);
+
+    assert_eq!(client.get_wrap(&alice, &202512).unwrap().archetype, builder);
+    assert_eq!(client.get_wrap(&bob, &202512).unwrap().archetype, builder);
+    assert_eq!(
+        client.get_wrap(&bob, &202601).unwrap().archetype,
+        symbol_short!("defi")
+    );
+    assert_eq!(client.archetype_count(&architect), 0);
+    assert_eq!(client.archetype_count(&builder), 2);
+    assert_eq!(client.archetype_count(&symbol_short!("defi")), 1);
+    assert!(client.list_archetypes().contains(&builder));
+
+    client.revoke_wrap(&alice, &202512);
+    assert_eq!(client.archetype_count(&builder), 1);
+}
+
+#[test]
+fn test_periods_with_archetype() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let builder = symbol_short!("builder");
+    let trader = symbol_short!("trader");
+
+    mint(&env, &client, &signing_key, &user, 202512, &builder, &hash);
+    mint(&env, &client, &signing_key, &user, 202510, &trader, &hash);
+    mint(&env, &client, &signing_key, &user, 202511, &builder, &hash);
+
+    assert_eq!(
+        client.periods_with_archetype(&user, &builder),
+        vec![&env, 202511u64, 202512u64]
+    );
+    assert_eq!(
+        client.periods_with_archetype(&user, &trader),
+        vec![&env, 202510u64]
+    );
+    assert_eq!(
+        client.periods_with_archetype(&user, &symbol_short!("defi")),
+        Vec::new(&env)
+    );
+}
+
+#[test]
+fn test_wrap_attributes() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let rank = symbol_short!("rank");
+
+    mint(&env, &client, &signing_key, &user, 202512, &arch, &hash);
+    assert_eq!(client.get_attribute(&user, &202512, &rank), None);
+
+    client.set_attribute(&user, &202512, &rank, &symbol_short!("gold"));
+    assert_eq!(
+        client.get_attribute(&user, &202512, &rank),
+        Some(symbol_short!("gold"))
+    );
+
+    client.set_attribute(&user, &202512, &rank, &symbol_short!("silver"));
+    assert_eq!(
+        client.get_attribute(&user, &202512, &rank),
+        Some(symbol_short!("silver"))
+    );
+
+    assert_eq!(
+        client.try_set_attribute(&user, &202601, &rank, &symbol_short!("gold")),
+        Err(Ok(ContractError::WrapNotFound.into()))
+    );
+}
+
+#[test]
+fn test_reads_decode_original_four_field_records() {
+    use crate::storage_types::{DataKey, LegacyU64WrapRecord};
+
+    let env = Env::default();
+    let (client, signing_key, admin) = setup(&env);
+    let user = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[6u8; 32]);
+
+    // Inject a record and signing key the way the original deployment stored them
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(
+            &DataKey::Wrap(user.clone(), 202401),
+            &LegacyU64WrapRecord {
+                timestamp: 1_700_000_000,
+                data_hash: hash.clone(),
+                archetype: symbol_short!("builder"),
+                period: 202401,
+            },
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::UserPeriods(user.clone()), &vec![&env, 202401u64]);
+        env.storage()
+            .persistent()
+            .set(&DataKey::WrapCount(user.clone()), &1u32);
+        env.storage().instance().set(
+            &DataKey::AdminPubKey,
+            &BytesN::from_array(&env, &signing_key.verifying_key().to_bytes()),
+        );
+    });
+
+    let record = client.get_wrap(&user, &202401).unwrap();
+    assert_eq!(record.timestamp, 1_700_000_000);
+    assert_eq!(record.data_hash, hash);
+    assert_eq!(record.archetype, symbol_short!("builder"));
+    assert_eq!(record.hash_algo, symbol_short!("sha256"));
+    assert_eq!(record.ledger_seq, 0);
+    assert_eq!(record.global_id, u64::MAX);
+    assert_eq!(record.minted_by, admin);
+    assert_eq!(record.signer_index, None);
+    assert_eq!(
+        client.get_wrap_checked(&user, &202401),
+        Some(record.clone())
+    );
+    assert_eq!(client.first_wrap(&user), Some(record.clone()));
+    assert_eq!(client.get_profile(&user).latest_period, Some(202401));
+
+    // The bare key reads back as slot 0 and still signs mints
+    let slots = client.signing_key_slots();
+    assert_eq!(slots.len(), 1);
+    assert!(slots.get_unchecked(0).is_some());
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202402,
+        &symbol_short!("builder"),
+        &hash,
+    );
+    assert_eq!(client.balance_of(&user), 2);
+
+    // Rewriting the old record stores it in the current shape
+    client.rename_archetype(
+        &symbol_short!("builder"),
+        &symbol_short!("maker"),
+        &vec![&env, (user.clone(), 202401u64)],
+    );
+    env.as_contract(&client.address, || {
+        let stored: WrapRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Wrap(user.clone(), 202401))
+            .unwrap();
+        assert_eq!(stored.archetype, symbol_short!("maker"));
+    });
+}
+
+#[test]
+fn test_backfill_index_adopts_unindexed_wraps() {
+    use crate::storage_types::{DataKey, LegacyU64WrapRecord};
+
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[6u8; 32]);
+
+    // Wraps from before the period index: a record and its count, but no UserPeriods
+    env.as_contract(&client.address, || {
+        for period in [202401u64, 202402] {
+            env.storage().persistent().set(
+                &DataKey::Wrap(user.clone(), period),
+                &LegacyU64WrapRecord {
+                    timestamp: 1_700_000_000,
+                    data_hash: hash.clone(),
+                    archetype: symbol_short!("builder"),
+                    period,
+                },
+            );
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::WrapCount(user.clone()), &2u32);
+    });
+    assert!(client.list_periods(&user).is_empty());
+    assert!(!client.count_matches_index(&user));
+
+    let mut oversized = Vec::new(&env);
+    for _ in 0..=MAX_BATCH {
+        oversized.push_back((user.clone(), 202401u64));
+    }
+    assert_eq!(
+        client.try_backfill_index(&oversized),
+        Err(Ok(ContractError::BatchTooLarge.into()))
+    );
+
+    // Missing and already-indexed entries are skipped
+    let entries = vec![
+        &env,
+        (user.clone(), 202402u64),
+        (user.clone(), 202401u64),
+        (user.clone(), 202401u64),
+        (user.clone(), 202403u64),
+    ];
+    assert_eq!(client.backfill_index(&entries), 2);
+    assert_eq!(client.list_periods(&user), vec![&env, 202401u64, 202402]);
+    assert!(client.count_matches_index(&user));
+    assert_eq!(client.first_wrap(&user).unwrap().period, 202401);
+    assert_eq!(client.recount_user(&user), 2);
+    assert_eq!(client.backfill_index(&entries), 0);
+
+    // The backfilled wraps count toward the contract-wide stats
+    let stats = client.storage_stats();
+    assert_eq!(stats.total_supply, 2);
+    assert_eq!(stats.holder_count, 1);
+    assert_eq!(stats.distinct_periods, 2);
+    assert_eq!(client.archetype_count(&symbol_short!("builder")), 2);
+    assert_eq!(client.top_holders(&1), vec![&env, (user.clone(), 2u32)]);
+
+    // A pre-index holder who mints before being backfilled is counted once
+    let early = Address::generate(&env);
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(
+            &DataKey::Wrap(early.clone(), 202401),
+            &LegacyU64WrapRecord {
+                timestamp: 1_700_000_000,
+                data_hash: hash.clone(),
+                archetype: symbol_short!("builder"),
+                period: 202401,
+            },
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::WrapCount(early.clone()), &1u32);
+    });
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &early,
+        202402,
+        &symbol_short!("builder"),
+        &hash,
+    );
+    assert_eq!(client.storage_stats().holder_count, 2);
+    client.backfill_index(&vec![&env, (early.clone(), 202401u64)]);
+    let stats = client.storage_stats();
+    assert_eq!(stats.total_supply, 4);
+    assert_eq!(stats.holder_count, 2);
+
+    // Revoking every wrap drops the holder again
+    client.revoke_wrap(&early, &202401);
+    client.revoke_wrap(&early, &202402);
+    let stats = client.storage_stats();
+    assert_eq!(stats.total_supply, 2);
+    assert_eq!(stats.holder_count, 1);
+}
+
+#[test]
+fn test_get_wrap_checked_reports_corrupt_record() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    mint(&env, &client, &signing_key, &user, 202512, &arch, &hash);
+    assert_eq!(
+        client.get_wrap_checked(&user, &202512),
+        client.get_wrap(&user, &202512)
+    );
+    assert_eq!(client.get_wrap_checked(&user, &202601), None);
+
+    // Write a value of the wrong shape under a Wrap key
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(
+            &DataKey::Wrap(user.clone(), 202602),
+            &symbol_short!("garbage"),
+        );
+    });
+    assert_eq!(
+        client.try_get_wrap_checked(&user, &202602),
+        Err(Ok(ContractError::CorruptRecord))
+    );
+}
+
+#[test]
+fn test_total_supply_tracks_mints_and_revokes() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let user = Address::generate(&env);
+    assert_eq!(client.total_supply(), 0);
+
+    mint(&env, &client, &signing_key, &user, 202511, &arch, &hash);
+    mint(&env, &client, &signing_key, &user, 202512, &arch, &hash);
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &Address::generate(&env),
+        202512,
+        &arch,
+        &hash,
+    );
+    assert_eq!(client.total_supply(), 3);
+
+    client.revoke_wrap(&user, &202511);
+    assert_eq!(client.total_supply(), 2);
+    assert_eq!(client.storage_stats().total_supply, 2);
+}
+
+#[test]
+fn test_max_supply_caps_minting() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    client.set_max_supply(&2);
+    assert_eq!(client.get_config().max_supply, 2);
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &Address::generate(&env),
+        202512,
+        &arch,
+        &hash,
+    );
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &Address::generate(&env),
+        202512,
+        &arch,
+        &hash,
+    );
+
+    let user = Address::generate(&env);
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202512,
+        &arch,
+        &hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap(&user, &202512, &arch, &hash, &signature, &0, &None),
+        Err(Ok(ContractError::MaxSupplyReached.into()))
+    );
+
+    // The batch path is capped too
+    let entries = signed_entries(&env, &client, &signing_key, &user, 1);
+    assert_eq!(
+        client.try_mint_wrap_batch(&entries),
+        Err(Ok(ContractError::MaxSupplyReached.into()))
+    );
+
+    // Lifting the cap reopens minting
+    client.set_max_supply(&0);
+    client.mint_wrap(&user, &202512, &arch, &hash, &signature, &0, &None);
+    assert_eq!(client.total_supply(), 3);
+}
+
+#[test]
+fn test_migrate_wraps_moves_all_periods() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+
+    mint(&env, &client, &signing_key, &from, 202511, &arch, &hash);
+    mint(&env, &client, &signing_key, &from, 202512, &arch, &hash);
+    let original = client.get_wrap(&from, &202512).unwrap();
+    client.acknowledge(&from, &202512);
+
+    assert_eq!(client.migrate_wraps(&from, &to), 2);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (
+            symbol_short!("wrap"),
+            symbol_short!("migrate"),
+            from.clone(),
+            to.clone()
+        )
+            .into_val(&env)
+    );
+    let (_, moved): (u32, u32) = data.try_into_val(&env).unwrap();
+    assert_eq!(moved, 2);
+
+    assert_eq!(client.balance_of(&from), 0);
+    assert_eq!(client.list_periods(&from), Vec::new(&env));
+    assert!(client.get_wrap(&from, &202511).is_none());
+    assert_eq!(client.balance_of(&to), 2);
+    assert_eq!(client.list_periods(&to), vec![&env, 202511u64, 202512u64]);
+    assert_eq!(client.get_wrap(&to, &202512).unwrap(), original);
+    assert!(!client.is_acknowledged(&from, &202512));
+    assert!(client.is_acknowledged(&to, &202512));
+    assert!(!client.is_acknowledged(&to, &202511));
+    assert_eq!(client.storage_stats().holder_count, 1);
+    assert_eq!(client.total_supply(), 2);
+}
+
+#[test]
+fn test_migrate_wraps_rejects_conflicting_period() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+
+    mint(&env, &client, &signing_key, &from, 202511, &arch, &hash);
+    mint(&env, &client, &signing_key, &from, 202512, &arch, &hash);
+    mint(&env, &client, &signing_key, &to, 202512, &arch, &hash);
+
+    assert_eq!(
+        client.try_migrate_wraps(&from, &to),
+        Err(Ok(ContractError::WrapAlreadyExists.into()))
+    );
+    assert_eq!(client.balance_of(&from), 2);
+    assert_eq!(client.balance_of(&to), 1);
+}
+
+#[test]
+fn test_migrate_wraps_respects_period_limit() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+
+    mint(&env, &client, &signing_key, &from, 202511, &arch, &hash);
+    mint(&env, &client, &signing_key, &from, 202512, &arch, &hash);
+    mint(&env, &client, &signing_key, &to, 202601, &arch, &hash);
+
+    client.set_max_periods_per_user(&2);
+    assert_eq!(
+        client.try_migrate_wraps(&from, &to),
+        Err(Ok(ContractError::UserPeriodLimit.into()))
+    );
+    assert_eq!(client.balance_of(&from), 2);
+    assert_eq!(client.balance_of(&to), 1);
+
+    // Exactly at the limit is fine
+    client.set_max_periods_per_user(&3);
+    assert_eq!(client.migrate_wraps(&from, &to), 2);
+}
+
+#[test]
+fn test_initialize_checked_matches_contract_id() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, StellarWrapContract);
+    let client = StellarWrapContractClient::new(&env, &contract_id);
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    let admin_pubkey = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+    let admin = Address::generate(&env);
+
+    let wrong_id = BytesN::from_array(&env, &[3u8; 32]);
+    assert_eq!(
+        client.try_initialize_checked(&admin, &admin_pubkey, &wrong_id),
+        Err(Ok(ContractError::WrongContract.into()))
+    );
+    assert!(!client.is_initialized());
+
+    let mut id = [0u8; 32];
+    contract_id
+        .clone()
+        .to_xdr(&env)
+        .slice(8..40)
+        .copy_into_slice(&mut id);
+    client.initialize_checked(&admin, &admin_pubkey, &BytesN::from_array(&env, &id));
+    assert_eq!(client.get_admin(), Some(admin));
+}
+
+#[test]
+fn test_renounce_admin_controls_keeps_minting() {
+    let env = Env::default();
+    let (client, signing_key, admin) = setup(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let user = Address::generate(&env);
+
+    client.renounce_admin_controls();
+
+    let renounced = Err(Ok(ContractError::ControlsRenounced.into()));
+    assert_eq!(client.try_update_admin(&Address::generate(&env)), renounced);
+    assert_eq!(
+        client.try_add_signing_key(&BytesN::from_array(&env, &[5u8; 32])),
+        renounced
+    );
+    assert_eq!(
+        client.try_upgrade(&BytesN::from_array(&env, &[0u8; 32])),
+        renounced
+    );
+    assert_eq!(client.try_set_paused(&true), renounced);
+    assert_eq!(client.try_set_max_supply(&1), renounced);
+
+    // Minting and reads continue
+    mint(&env, &client, &signing_key, &user, 202512, &arch, &hash);
+    client.mint_by(&admin, &user, &202601, &arch, &hash);
+    client.mint_wrap_batch(&signed_entries(&env, &client, &signing_key, &user, 2));
+    assert_eq!(client.balance_of(&user), 4);
+    assert_eq!(client.get_admin(), Some(admin));
+}
+
+#[test]
+fn test_global_ids_follow_mint_order() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    mint(&env, &client, &signing_key, &alice, 202512, &arch, &hash);
+    mint(&env, &client, &signing_key, &bob, 202512, &arch, &hash);
+    mint(&env, &client, &signing_key, &alice, 202511, &arch, &hash);
+
+    assert_eq!(client.get_wrap(&alice, &202512).unwrap().global_id, 0);
+    assert_eq!(client.get_wrap(&bob, &202512).unwrap().global_id, 1);
+    assert_eq!(client.get_wrap(&alice, &202511).unwrap().global_id, 2);
+}
+
+#[test]
+fn test_sbt_operations_are_rejected_with_event() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+
+    assert_eq!(
+        client.try_transfer(&from, &to, &1),
+        Err(Ok(ContractError::SbtTransferNotAllowed))
+    );
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (
+            symbol_short!("wrap"),
+            Symbol::new(&env, "sbt_reject"),
+            from.clone()
+        )
+            .into_val(&env)
+    );
+    let (_, reason): (u32, Symbol) = data.try_into_val(&env).unwrap();
+    assert_eq!(reason, symbol_short!("transfer"));
+
+    assert_eq!(
+        client.try_approve(&from, &to, &1, &100),
+        Err(Ok(ContractError::SbtTransferNotAllowed))
+    );
+    assert_eq!(
+        client.try_burn(&from, &1),
+        Err(Ok(ContractError::SbtTransferNotAllowed))
+    );
+}
+
+#[test]
+fn test_preflight_batch_reports_each_entry() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+
+    // The first entry's period is already taken, the second reuses a spent signature
+    let taken = signed_entries(&env, &client, &signing_key, &user, 1);
+    client.mint_wrap_batch(&taken);
+    let spent = taken.get(0).unwrap();
+    let mut used_signature = signed_entries(&env, &client, &signing_key, &user, 2)
+        .get(1)
+        .unwrap();
+    used_signature.signature = spent.signature.clone();
+    let other = Address::generate(&env);
+    let fresh = signed_entries(&env, &client, &signing_key, &other, 1)
+        .get(0)
+        .unwrap();
+
+    let mut period_taken = spent.clone();
+    period_taken.signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        spent.period,
+        &symbol_short!("other"),
+        &spent.data_hash,
+    );
+    period_taken.archetype = symbol_short!("other");
+
+    // Signed by a key that isn't in slot 0
+    let impostor = SigningKey::from_bytes(&[10u8; 32]);
+    let mut forged = signed_entries(&env, &client, &impostor, &other, 2)
+        .get(1)
+        .unwrap();
+    forged.key_index = 0;
+
+    let entries = vec![
+        &env,
+        period_taken,
+        used_signature,
+        fresh.clone(),
+        fresh,
+        forged,
+    ];
+    assert_eq!(
+        client.preflight_batch(&entries),
+        vec![
+            &env,
+            Err(ContractError::WrapAlreadyExists),
+            Err(ContractError::SignatureReused),
+            Ok(()),
+            Err(ContractError::SignatureReused),
+            Err(ContractError::InvalidSignature),
+        ]
+    );
+
+    // Nothing was written
+    assert_eq!(client.balance_of(&other), 0);
+}
+
+#[test]
+fn test_preflight_batch_counts_earlier_entries() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let entries = signed_entries(&env, &client, &signing_key, &user, 3);
+    let ok3 = vec![&env, Ok(()), Ok(()), Ok(())];
+    assert_eq!(client.preflight_batch(&entries), ok3);
+
+    // Each cap counts the entries accepted before it, as the real batch would
+    client.set_ledger_mint_cap(&2);
+    let capped = vec![&env, Ok(()), Ok(()), Err(ContractError::GlobalRateExceeded)];
+    assert_eq!(client.preflight_batch(&entries), capped);
+    assert!(client.try_mint_wrap_batch(&entries).is_err());
+    client.set_ledger_mint_cap(&0);
+
+    client.set_max_supply(&2);
+    let supply_capped = vec![&env, Ok(()), Ok(()), Err(ContractError::MaxSupplyReached)];
+    assert_eq!(client.preflight_batch(&entries), supply_capped);
+    client.set_max_supply(&0);
+
+    client.set_max_periods_per_user(&2);
+    let user_capped = vec![&env, Ok(()), Ok(()), Err(ContractError::UserPeriodLimit)];
+    assert_eq!(client.preflight_batch(&entries), user_capped);
+    client.set_max_periods_per_user(&500);
+
+    // Every entry reuses one data_hash, so strict mode accepts only the first
+    client.set_strict_hashes(&true);
+    let duplicates = vec![
+        &env,
+        Ok(()),
+        Err(ContractError::DuplicateHash),
+        Err(ContractError::DuplicateHash),
+    ];
+    assert_eq!(client.preflight_batch(&entries), duplicates);
+    client.set_strict_hashes(&false);
+
+    // An empty signing key slot can never verify
+    let mut bad_slot = entries.get(0).unwrap();
+    bad_slot.key_index = 5;
+    assert_eq!(
+        client.preflight_batch(&vec![&env, bad_slot]),
+        vec![&env, Err(ContractError::InvalidSignature)]
+    );
+
+    assert_eq!(client.balance_of(&user), 0);
+}
+
+#[test]
+fn test_format_period() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+
+    assert_eq!(
+        client.format_period(&202401),
+        String::from_str(&env, "2024-01")
+    );
+    assert_eq!(
+        client.format_period(&202512),
+        String::from_str(&env, "2025-12")
+    );
+    assert_eq!(client.format_period(&0), String::from_str(&env, "all-time"));
+    assert_eq!(
+        client.try_format_period(&202413),
+        Err(Ok(ContractError::InvalidPeriod.into()))
+    );
+}
+
+#[test]
+fn test_acknowledge_wrap() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    mint(&env, &client, &signing_key, &user, 202512, &arch, &hash);
+    assert!(!client.is_acknowledged(&user, &202512));
+
+    client.acknowledge(&user, &202512);
+    assert!(client.is_acknowledged(&user, &202512));
+
+    assert_eq!(
+        client.try_acknowledge(&user, &202601),
+        Err(Ok(ContractError::WrapNotFound.into()))
+    );
+
+    // Revoking clears the acknowledgment, so a re-mint starts unacknowledged
+    client.revoke_wrap(&user, &202512);
+    assert!(!client.is_acknowledged(&user, &202512));
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202512,
+        &arch,
+        &BytesN::from_array(&env, &[2u8; 32]),
+    );
+    assert!(!client.is_acknowledged(&user, &202512));
+}
+
+#[test]
+fn test_acknowledge_by_third_party_fails() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    mint(&env, &client, &signing_key, &user, 202512, &arch, &hash);
+
+    // Only the stranger signs, so the holder's auth is missing
+    env.mock_auths(&[MockAuth {
+        address: &stranger,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "acknowledge",
+            args: (user.clone(), 202512u64).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_acknowledge(&user, &202512).is_err());
+    assert!(!client.is_acknowledged(&user, &202512));
+}
+
+#[test]
+fn test_weighted_score() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    client.set_archetype_weight(&symbol_short!("architect"), &5);
+    client.set_archetype_weight(&symbol_short!("defi"), &2);
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202511,
+        &symbol_short!("architect"),
+        &hash,
+    );
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202512,
+        &symbol_short!("defi"),
+        &hash,
+    );
+    assert_eq!(client.weighted_score(&user), 7);
+
+    // Archetypes without a weight count 1
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202601,
+        &symbol_short!("trader"),
+        &hash,
+    );
+    assert_eq!(client.weighted_score(&user), 8);
+    assert_eq!(client.get_profile(&user).total_score, 8);
+}
+
+#[test]
+fn test_private_mode_restricts_get_wrap() {
+    let env = Env::default();
+    let (client, signing_key, admin) = setup(&env);
+    let user = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    mint(&env, &client, &signing_key, &user, 202512, &arch, &hash);
+
+    // Public mode: anyone may read
+    assert_eq!(
+        client.get_wrap_as(&stranger, &user, &202512),
+        client.get_wrap(&user, &202512)
+    );
+
+    client.set_private_mode(&true);
+
+    // The owner can read their own private wrap, and so can the admin
+    assert!(client.get_wrap(&user, &202512).is_some());
+    assert!(client.get_wrap_as(&user, &user, &202512).is_some());
+    assert!(client.get_wrap_as(&admin, &user, &202512).is_some());
+    assert_eq!(
+        client.try_get_wrap_as(&stranger, &user, &202512),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    // Every read of record contents needs the owner too, not just get_wrap
+    assert!(client.get_wrap_checked(&user, &202512).is_some());
+    assert!(!client.export_user(&user).is_empty());
+
+    // A stranger's signature does not stand in for the owner's
+    env.mock_auths(&[MockAuth {
+        address: &stranger,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "export_user",
+            args: (user.clone(),).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_export_user(&user).is_err());
+
+    // Without the owner's signature the record reads fail
+    env.set_auths(&[]);
+    assert!(client.try_get_wrap(&user, &202512).is_err());
+    assert!(client.try_get_wrap_checked(&user, &202512).is_err());
+    assert!(client.try_export_user(&user).is_err());
+    assert!(client.try_first_wrap(&user).is_err());
+    assert!(client.try_token_uri(&user, &202512).is_err());
+    assert!(client.try_get_minter_of(&user, &202512).is_err());
+    assert!(client.try_get_hash_algo(&user, &202512).is_err());
+
+    // Counts and the period index stay public
+    assert_eq!(client.balance_of(&user), 1);
+    assert_eq!(client.list_periods(&user).len(), 1);
+}
+
+#[test]
+fn test_span_periods() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    let contiguous = Address::generate(&env);
+    for period in 202501..=202503u64 {
+        mint(
+            &env,
+            &client,
+            &signing_key,
+            &contiguous,
+            period,
+            &arch,
+            &hash,
+        );
+    }
+    assert_eq!(client.span_periods(&contiguous), (3, 2));
+
+    let gapped = Address::generate(&env);
+    for period in [202501u64, 202505, 202510] {
+        mint(&env, &client, &signing_key, &gapped, period, &arch, &hash);
+    }
+    // The all-time wrap does not stretch the span
+    client.update_alltime(&gapped, &arch, &hash);
+    assert_eq!(client.span_periods(&gapped), (3, 9));
+
+    assert_eq!(client.span_periods(&Address::generate(&env)), (0, 0));
+}
+
+#[test]
+fn test_list_periods_since() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    for (period, timestamp) in [(202510u64, 1_000u64), (202511, 2_000), (202512, 3_000)] {
+        env.ledger().with_mut(|li| li.timestamp = timestamp);
+        mint(&env, &client, &signing_key, &user, period, &arch, &hash);
+    }
+
+    assert_eq!(
+        client.list_periods_since(&user, &2_500),
+        vec![&env, 202512u64]
+    );
+    assert_eq!(
+        client.list_periods_since(&user, &2_000),
+        vec![&env, 202511u64, 202512u64]
+    );
+    assert_eq!(client.list_periods_since(&user, &3_001), Vec::new(&env));
+}
+
+#[test]
+fn test_max_periods_per_user() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    assert_eq!(client.get_config().max_periods_per_user, 500);
+
+    client.set_max_periods_per_user(&3);
+    for period in 1..=3u64 {
+        mint(&env, &client, &signing_key, &user, period, &arch, &hash);
+    }
+
+    let signature = sign_payload(&env, &signing_key, &client.address, &user, 4, &arch, &hash);
+    assert_eq!(
+        client.try_mint_wrap(&user, &4, &arch, &hash, &signature, &0, &None),
+        Err(Ok(ContractError::UserPeriodLimit.into()))
+    );
+
+    // Other users are unaffected
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &Address::generate(&env),
+        4,
+        &arch,
+        &hash,
+    );
+}
+
+#[test]
+fn test_mint_wrap_linked() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    // The link is one of the signed terms
+    let linked_signature = |user: &Address, period: u64, linked: u64| {
+        let mut payload = mint_payload_bytes(&env, &client.address, user, period, &arch, &hash);
+        payload.append(&symbol_short!("linked").to_xdr(&env));
+        payload.append(&linked.to_xdr(&env));
+        sign_bytes(&env, &signing_key, &payload)
+    };
+
+    mint(&env, &client, &signing_key, &user, 202511, &arch, &hash);
+    assert_eq!(client.get_linked(&user, &202511), None);
+
+    let signature = linked_signature(&user, 202512, 202511);
+    // The holder can't drop or swap the admin's link
+    assert!(client
+        .try_mint_wrap(&user, &202512, &arch, &hash, &signature, &0, &None)
+        .is_err());
+    assert!(client
+        .try_mint_wrap_linked(&user, &202512, &arch, &hash, &signature, &0, &Some(202510))
+        .is_err());
+    client.mint_wrap_linked(&user, &202512, &arch, &hash, &signature, &0, &Some(202511));
+    assert_eq!(client.get_linked(&user, &202512), Some(202511));
+    assert_eq!(
+        client.get_wrap(&user, &202512).unwrap().linked_period,
+        Some(202511)
+    );
+
+    // A plain signature can't have a link attached
+    let plain = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202601,
+        &arch,
+        &hash,
+    );
+    assert!(client
+        .try_mint_wrap_linked(&user, &202601, &arch, &hash, &plain, &0, &Some(202511))
+        .is_err());
+
+    // Dangling links, including one to another user's wrap, are rejected
+    let signature = linked_signature(&user, 202601, 202410);
+    assert_eq!(
+        client.try_mint_wrap_linked(&user, &202601, &arch, &hash, &signature, &0, &Some(202410)),
+        Err(Ok(ContractError::LinkedPeriodMissing.into()))
+    );
+    let other = Address::generate(&env);
+    let signature = linked_signature(&other, 202601, 202511);
+    assert_eq!(
+        client.try_mint_wrap_linked(&other, &202601, &arch, &hash, &signature, &0, &Some(202511)),
+        Err(Ok(ContractError::LinkedPeriodMissing.into()))
+    );
+}
+
+#[test]
+fn test_reemit_mint() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    assert_eq!(
+        client.try_reemit_mint(&user, &202512),
+        Err(Ok(ContractError::WrapNotFound.into()))
+    );
+
+    mint(&env, &client, &signing_key, &user, 202512, &arch, &hash);
+    client.reemit_mint(&user, &202512);
+
+    let (contract, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(contract, client.address);
+    assert_eq!(
+        topics,
+        (
+            symbol_short!("wrap"),
+            symbol_short!("mint"),
+            user.clone(),
+            202512u64
+        )
+            .into_val(&env)
+    );
+    let (schema, archetype, replayed): (u32, Symbol, bool) = data.into_val(&env);
+    assert_eq!(schema, EVENT_SCHEMA);
+    assert_eq!(
+        archetype,
+        client.get_wrap(&user, &202512).unwrap().archetype
+    );
+    assert!(replayed);
+}
+
+#[test]
+fn test_set_symbol() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+    assert_eq!(client.symbol(), String::from_str(&env, "WRAP"));
+
+    client.set_symbol(&String::from_str(&env, "SWRP"));
+    assert_eq!(client.symbol(), String::from_str(&env, "SWRP"));
+    assert_eq!(client.metadata().symbol, String::from_str(&env, "SWRP"));
+}
+
+#[test]
+fn test_mint_rejects_period_zero() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    let signature = sign_payload(&env, &signing_key, &client.address, &user, 0, &arch, &hash);
+    assert_eq!(
+        client.try_mint_wrap(&user, &0, &arch, &hash, &signature, &0, &None),
+        Err(Ok(ContractError::InvalidPeriod.into()))
+    );
+    assert_eq!(client.get_wrap(&user, &0), None);
+
+    mint(&env, &client, &signing_key, &user, 202512, &arch, &hash);
+    assert!(client.get_wrap(&user, &202512).is_some());
+}
+
+#[test]
+fn test_get_minter_of() {
+    let env = Env::default();
+    let (client, signing_key, admin) = setup(&env);
+    let minter = Address::generate(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    client.set_minter(&minter);
+    mint(&env, &client, &signing_key, &user, 202512, &arch, &hash);
+    client.mint_by(&minter, &user, &202601, &arch, &hash);
+
+    assert_eq!(client.get_minter_of(&user, &202512), Some(admin));
+    assert_eq!(client.get_minter_of(&user, &202601), Some(minter));
+    assert_eq!(client.get_minter_of(&user, &202602), None);
+
+    // Only signature-based mints record a signing key slot
+    assert_eq!(
+        client.get_wrap(&user, &202512).unwrap().signer_index,
+        Some(0)
+    );
+    assert_eq!(client.get_wrap(&user, &202601).unwrap().signer_index, None);
+}
+
+#[test]
+fn test_count_diff() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    for period in [202510, 202511, 202512] {
+        mint(&env, &client, &signing_key, &a, period, &arch, &hash);
+    }
+    mint(&env, &client, &signing_key, &b, 202512, &arch, &hash);
+
+    assert_eq!(client.count_diff(&a, &b), 2);
+    assert_eq!(client.count_diff(&b, &a), -2);
+    assert_eq!(client.count_diff(&a, &a), 0);
+}
+
+#[test]
+fn test_strict_hashes() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    // Lenient by default: the same hash may repeat across periods
+    mint(&env, &client, &signing_key, &user, 202510, &arch, &hash);
+    mint(&env, &client, &signing_key, &user, 202511, &arch, &hash);
+
+    client.set_strict_hashes(&true);
+    mint(&env, &client, &signing_key, &user, 202512, &arch, &hash);
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202601,
+        &arch,
+        &hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap(&user, &202601, &arch, &hash, &signature, &0, &None),
+        Err(Ok(ContractError::DuplicateHash.into()))
+    );
+
+    // Other users and other hashes are unaffected
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &Address::generate(&env),
+        202601,
+        &arch,
+        &hash,
+    );
+    let other_hash = BytesN::from_array(&env, &[2u8; 32]);
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202601,
+        &arch,
+        &other_hash,
+    );
+}
+
+#[test]
+fn test_max_supply_applies_to_existing_holders() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    client.set_max_supply(&2);
+    mint(&env, &client, &signing_key, &user, 202511, &arch, &hash);
+    mint(&env, &client, &signing_key, &user, 202512, &arch, &hash);
+
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202601,
+        &arch,
+        &hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap(&user, &202601, &arch, &hash, &signature, &0, &None),
+        Err(Ok(ContractError::MaxSupplyReached.into()))
+    );
+
+    // The cap counts outstanding wraps, so a revoke frees a slot
+    client.revoke_wrap(&user, &202511);
+    client.mint_wrap(&user, &202601, &arch, &hash, &signature, &0, &None);
+    assert_eq!(client.total_supply(), 2);
+}
+
+#[test]
+fn test_balance_and_latest() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    assert_eq!(client.balance_and_latest(&user), (0, None));
+
+    for period in [202601, 202511, 202512] {
+        mint(&env, &client, &signing_key, &user, period, &arch, &hash);
+    }
+    assert_eq!(client.balance_and_latest(&user), (3, Some(202601)));
+}
+
+#[test]
+fn test_rank_in_cohort() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+
+    for (user, wraps) in [(&first, 3u64), (&second, 2), (&third, 1)] {
+        for period in 202601..202601 + wraps {
+            mint(&env, &client, &signing_key, user, period, &arch, &hash);
+        }
+    }
+
+    let cohort = vec![&env, third.clone(), first.clone(), second.clone()];
+    assert_eq!(client.rank_in(&first, &cohort), Some(1));
+    assert_eq!(client.rank_in(&second, &cohort), Some(2));
+    assert_eq!(client.rank_in(&third, &cohort), Some(3));
+    assert_eq!(client.rank_in(&Address::generate(&env), &cohort), None);
+
+    let mut oversized = Vec::new(&env);
+    for _ in 0..=MAX_BATCH {
+        oversized.push_back(first.clone());
+    }
+    assert_eq!(
+        client.try_rank_in(&first, &oversized),
+        Err(Ok(ContractError::BatchTooLarge.into()))
+    );
+}
+
+#[test]
+fn test_upgrade_emits_event() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+
+    // Smallest module the host accepts: just the env meta section for protocol 21
+    let module = b"\0asm\x01\0\0\0\0\x1e\x11contractenvmetav0\0\0\0\0\0\0\0\x15\0\0\0\0";
+    let wasm_hash = env
+        .deployer()
+        .upload_contract_wasm(Bytes::from_slice(&env, module));
+
+    client.upgrade(&wasm_hash);
+
+    let (contract, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(contract, client.address);
+    assert_eq!(
+        topics,
+        (symbol_short!("wrap"), symbol_short!("upgrade")).into_val(&env)
+    );
+    let (schema, hash, version): (u32, BytesN<32>, u32) = data.into_val(&env);
+    assert_eq!(schema, EVENT_SCHEMA);
+    assert_eq!(hash, wasm_hash);
+    assert_eq!(version, 1);
+}
+
+#[test]
+fn test_self_burn() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    mint(&env, &client, &signing_key, &user, 202511, &arch, &hash);
+    mint(&env, &client, &signing_key, &user, 202512, &arch, &hash);
+    assert_eq!(
+        client.try_burn(&user, &1),
+        Err(Ok(ContractError::SbtTransferNotAllowed))
+    );
+
+    client.set_allow_self_burn(&true);
+    client.burn(&user, &1);
+    assert_eq!(client.balance_of(&user), 1);
+    assert_eq!(client.get_wrap(&user, &202512), None);
+    assert_eq!(client.total_supply(), 1);
+
+    // Only the holder can authorize burning their wraps
+    env.mock_auths(&[MockAuth {
+        address: &other,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "burn",
+            args: (&user, 1i128).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_burn(&user, &1).is_err());
+    assert_eq!(client.balance_of(&user), 1);
+
+    // Transfers stay blocked
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_transfer(&user, &other, &1),
+        Err(Ok(ContractError::SbtTransferNotAllowed))
+    );
+}
+
+#[test]
+fn test_max_period_ceiling() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    client.set_max_period(&202612);
+    assert_eq!(client.get_config().max_period, 202612);
+    mint(&env, &client, &signing_key, &user, 202612, &arch, &hash);
+
+    let signature = sign_payload(
+        &env,
+        &signing_key,
+        &client.address,
+        &user,
+        202701,
+        &arch,
+        &hash,
+    );
+    assert_eq!(
+        client.try_mint_wrap(&user, &202701, &arch, &hash, &signature, &0, &None),
+        Err(Ok(ContractError::PeriodTooHigh.into()))
+    );
+
+    // Zero disables the ceiling
+    client.set_max_period(&0);
+    client.mint_wrap(&user, &202701, &arch, &hash, &signature, &0, &None);
+    assert_eq!(client.balance_of(&user), 2);
+}
+
+#[test]
+fn test_list_period_archetypes() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202502,
+        &symbol_short!("defi"),
+        &hash,
+    );
+    mint(
+        &env,
+        &client,
+        &signing_key,
+        &user,
+        202501,
+        &symbol_short!("architect"),
+        &hash,
+    );
+
+    assert_eq!(
+        client.list_period_archetypes(&user, &0, &10),
+        vec![
+            &env,
+            (202501u64, symbol_short!("architect")),
+            (202502u64, symbol_short!("defi"))
+        ]
+    );
+    assert_eq!(
+        client.list_period_archetypes(&user, &1, &10),
+        vec![&env, (202502u64, symbol_short!("defi"))]
+    );
+}
+
+#[test]
+fn test_global_stats() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    mint(&env, &client, &signing_key, &alice, 202511, &arch, &hash);
+    mint(&env, &client, &signing_key, &alice, 202512, &arch, &hash);
+    mint(&env, &client, &signing_key, &bob, 202512, &arch, &hash);
+    client.set_paused(&true);
+
+    assert_eq!(
+        client.stats(),
+        GlobalStats {
+            total_supply: 3,
+            unique_holders: 2,
+            version: 0,
+            paused: true,
+        }
+    );
+}
+
+#[test]
+fn test_count_matches_index() {
+    let env = Env::default();
+    let (client, signing_key, _) = setup(&env);
+    let user = Address::generate(&env);
+    let arch = symbol_short!("arch");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    assert!(client.count_matches_index(&user));
+    mint(&env, &client, &signing_key, &user, 202511, &arch, &hash);
+    mint(&env, &client, &signing_key, &user, 202512, &arch, &hash);
+    assert!(client.count_matches_index(&user));
+
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::WrapCount(user.clone()), &5u32);
+    });
+    assert!(!client.count_matches_index(&user));
+
+    client.recount_user(&user);
+    assert!(client.count_matches_index(&user));
+}